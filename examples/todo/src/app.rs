@@ -1,25 +1,20 @@
 use std::{collections::HashMap, ops::Deref, sync::Arc};
 
-use restify::prelude::*;
+use restify::{
+  axum::{Health, HealthModule},
+  prelude::*,
+};
 use tokio::sync::Mutex;
-use tower_http::trace::TraceLayer;
 
 use crate::todo::{entities::TodoEntity, TodoModule};
 
 #[derive(Module)]
-#[module(imports(TodoModule), controllers(AppController))]
+#[module(imports(
+  TodoModule,
+  HealthModule::new().check("store", AppState::store_health)
+))]
 pub struct AppModule;
 
-pub struct AppController;
-
-#[controller("/", wrap = TraceLayer::new_for_http())]
-impl AppController {
-  #[get]
-  async fn up() -> &'static str {
-    "UP!"
-  }
-}
-
 #[derive(Clone, Default)]
 pub struct AppState(Arc<AppStateInner>);
 
@@ -28,6 +23,14 @@ pub struct AppStateInner {
   pub store: Mutex<HashMap<String, TodoEntity>>,
 }
 
+impl AppState {
+  async fn store_health(self) -> Health {
+    let todos = self.store.lock().await.len();
+
+    Health::up().details(serde_json::json!({ "todos": todos }))
+  }
+}
+
 impl Deref for AppState {
   type Target = AppStateInner;
 
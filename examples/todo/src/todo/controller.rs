@@ -22,17 +22,19 @@ impl TodoController {
     Json(self.service.get_all().await)
   }
 
-  #[get("/:id")]
+  #[get("/{id}")]
   async fn get_one(self, Path((id,)): Path<(String,)>) -> Json<Option<TodoEntity>> {
     Json(self.service.get_one(id).await)
   }
 
   #[post]
-  async fn create(self, Json(dto): Json<CreateTodoDto>) -> Json<TodoEntity> {
-    Json(self.service.create(dto).await)
+  #[status(201)]
+  #[return_json]
+  async fn create(self, Json(dto): Json<CreateTodoDto>) -> TodoEntity {
+    self.service.create(dto).await
   }
 
-  #[patch("/:id")]
+  #[patch("/{id}")]
   async fn update(
     self,
     Path((id,)): Path<(String,)>,
@@ -41,7 +43,7 @@ impl TodoController {
     self.service.update(id, dto).await.map(Json)
   }
 
-  #[delete("/:id")]
+  #[delete("/{id}")]
   async fn delete(self, Path((id,)): Path<(String,)>) -> Result<Json<TodoEntity>, Response> {
     self.service.delete(id).await.map(Json)
   }
@@ -61,12 +61,12 @@ impl TodoService {
       return Ok(todo.clone());
     }
 
-    return Err(
+    Err(
       Response::builder()
         .status(StatusCode::NOT_FOUND)
         .body(().into())
         .unwrap(),
-    );
+    )
   }
 
   pub async fn delete(&self, id: String) -> Result<TodoEntity, Response> {
@@ -0,0 +1,457 @@
+use std::{
+  any::type_name,
+  env, fmt, fs, io,
+  marker::PhantomData,
+  ops::Deref,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use axum::{
+  async_trait,
+  extract::FromRequestParts,
+  http::{request::Parts, StatusCode},
+  response::{IntoResponse, Response},
+};
+use serde::de::DeserializeOwned;
+use toml::{Table, Value};
+
+use super::{Overrides, Routes};
+use crate::{Module, ModuleDetails, ProviderError, Providers};
+
+type Validator<T> = Arc<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+
+/// A module loading the configuration of the application into `T` at bootstrap, injected into
+/// handlers and injectables with [`Config<T>`]:
+///
+/// ```ignore
+/// #[derive(Module)]
+/// #[module(imports(
+///   ConfigModule::<AppConfig>::new()
+///     .file("config/app.toml")
+///     .env_prefix("APP")
+///     .environment_from("APP_ENV")
+/// ))]
+/// pub struct AppModule;
+/// ```
+///
+/// Sources are merged in this order, the last one winning:
+///
+/// 1. the TOML files, then their `<name>.<environment>.toml` variants (e.g. `app.production.toml`)
+///    if they exist,
+/// 2. `.env`, then `.env.<environment>`, if they exist,
+/// 3. the environment variables.
+///
+/// Variables are read only with [`env_prefix`](Self::env_prefix): `APP_PORT` sets `port` and
+/// `APP_DATABASE__URL` sets `database.url`. Their values are parsed as TOML values when possible
+/// (`8080`, `true`, `[1, 2]`), quote them to keep strings (`'"1.0"'`).
+///
+/// If the configuration can't be loaded or is invalid, `try_into_router` returns
+/// `RouterError::Provider` with the [`ConfigError`] and `into_router` panics.
+pub struct ConfigModule<T, S = (), Ctx = ()> {
+  files: Vec<(PathBuf, bool)>,
+  dotenv: Option<PathBuf>,
+  env_prefix: Option<String>,
+  environment: Option<Environment>,
+  validators: Vec<Validator<T>>,
+  _marker: PhantomData<fn() -> (S, Ctx)>,
+}
+
+enum Environment {
+  Name(String),
+  Var(String),
+}
+
+impl<T, S, Ctx> ConfigModule<T, S, Ctx>
+where
+  T: DeserializeOwned + Send + Sync + 'static,
+{
+  pub fn new() -> Self {
+    Self {
+      files: vec![],
+      dotenv: Some(".env".into()),
+      env_prefix: None,
+      environment: None,
+      validators: vec![],
+      _marker: PhantomData,
+    }
+  }
+
+  /// Adds a TOML file, which must exist.
+  pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+    self.files.push((path.into(), true));
+    self
+  }
+
+  /// Adds a TOML file, skipped if it doesn't exist.
+  pub fn optional_file(mut self, path: impl Into<PathBuf>) -> Self {
+    self.files.push((path.into(), false));
+    self
+  }
+
+  /// The `.env` file, relative to the working directory; `None` to ignore it.
+  pub fn dotenv(mut self, path: Option<impl Into<PathBuf>>) -> Self {
+    self.dotenv = path.map(Into::into);
+    self
+  }
+
+  /// Reads the variables starting with `<prefix>_`, from the environment and the `.env` files.
+  pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+    self.env_prefix = Some(prefix.into());
+    self
+  }
+
+  /// The environment of the application, e.g. `production`, selecting the files specific to it.
+  pub fn environment(mut self, name: impl Into<String>) -> Self {
+    self.environment = Some(Environment::Name(name.into()));
+    self
+  }
+
+  /// Reads the environment of the application from the `var` variable when loading.
+  pub fn environment_from(mut self, var: impl Into<String>) -> Self {
+    self.environment = Some(Environment::Var(var.into()));
+    self
+  }
+
+  /// Checks the loaded configuration, e.g. that a pool size isn't zero.
+  pub fn validate<F, E>(mut self, validate: F) -> Self
+  where
+    F: Fn(&T) -> Result<(), E> + Send + Sync + 'static,
+    E: fmt::Display,
+  {
+    self.validators.push(Arc::new(move |config| {
+      validate(config).map_err(|err| err.to_string())
+    }));
+    self
+  }
+
+  /// Loads and validates the configuration.
+  pub fn load(&self) -> Result<T, ConfigError> {
+    let environment = match &self.environment {
+      Some(Environment::Name(name)) => Some(name.clone()),
+      Some(Environment::Var(var)) => env::var(var).ok().filter(|name| !name.is_empty()),
+      None => None,
+    };
+
+    let mut table = Table::new();
+
+    for (path, required) in &self.files {
+      if let Some(file) = read_toml(path, *required)? {
+        merge(&mut table, file);
+      }
+
+      if let Some(environment) = &environment {
+        if let Some(file) = read_toml(&with_environment(path, environment), false)? {
+          merge(&mut table, file);
+        }
+      }
+    }
+
+    if let Some(prefix) = &self.env_prefix {
+      let mut vars = vec![];
+
+      if let Some(dotenv) = &self.dotenv {
+        vars.extend(read_dotenv(dotenv)?);
+
+        if let Some(environment) = &environment {
+          let mut name = dotenv.clone().into_os_string();
+          name.push(format!(".{environment}"));
+          vars.extend(read_dotenv(Path::new(&name))?);
+        }
+      }
+
+      vars.extend(env::vars().map(|(key, value)| (key, parse_value(&value))));
+
+      for (key, value) in vars {
+        if let Some(key) = key.strip_prefix(prefix).and_then(|k| k.strip_prefix('_')) {
+          insert_var(&mut table, key, value);
+        }
+      }
+    }
+
+    let config = T::deserialize(Value::Table(table)).map_err(ConfigError::Deserialize)?;
+
+    for validate in &self.validators {
+      validate(&config).map_err(ConfigError::Invalid)?;
+    }
+
+    Ok(config)
+  }
+}
+
+impl<T, S, Ctx> Default for ConfigModule<T, S, Ctx>
+where
+  T: DeserializeOwned + Send + Sync + 'static,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, S, Ctx> Module for ConfigModule<T, S, Ctx>
+where
+  T: DeserializeOwned + Send + Sync + 'static,
+  S: Clone + Send + Sync + 'static,
+{
+  type Context = Ctx;
+  type ControllerContext = ();
+  type ControllerReturn = Routes<S>;
+
+  fn details(&self, _ctx: &mut Ctx) -> ModuleDetails<Ctx, (), Routes<S>> {
+    let mut providers = Providers::new();
+
+    match self.load() {
+      Ok(config) => providers.insert(Config(Arc::new(config))),
+      Err(err) => providers.fail(ProviderError::new::<T>(err)),
+    }
+
+    ModuleDetails {
+      imports: vec![],
+      controllers: vec![],
+      gateways: vec![],
+      providers,
+    }
+  }
+}
+
+/// An error loading the configuration of a [`ConfigModule`].
+#[derive(Debug)]
+pub enum ConfigError {
+  /// A file can't be read.
+  Io(PathBuf, io::Error),
+  /// A TOML file is malformed.
+  Toml(PathBuf, toml::de::Error),
+  /// A line of a `.env` file isn't `KEY=VALUE`.
+  Dotenv(PathBuf, usize),
+  /// The merged sources don't match the configuration type.
+  Deserialize(toml::de::Error),
+  /// A validator of the module failed.
+  Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ConfigError::Io(path, err) => write!(f, "Failed to read `{}`: {err}", path.display()),
+      ConfigError::Toml(path, err) => write!(f, "Invalid TOML in `{}`: {err}", path.display()),
+      ConfigError::Dotenv(path, line) => {
+        write!(f, "Invalid line {line} in `{}`", path.display())
+      }
+      ConfigError::Deserialize(err) => write!(f, "{}", err.to_string().trim_end()),
+      ConfigError::Invalid(err) => write!(f, "Invalid configuration: {err}"),
+    }
+  }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The configuration loaded by a [`ConfigModule<T>`], also usable as a field of an injectable.
+pub struct Config<T>(pub Arc<T>);
+
+impl<T> Clone for Config<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<T> Deref for Config<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Config<T>
+where
+  T: Send + Sync + 'static,
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    Overrides::resolve_provider::<Config<T>>(parts)
+      .map(|config| (*config).clone())
+      .ok_or_else(|| {
+        let msg = format!(
+          "Missing configuration `{}`, import `ConfigModule::<{0}>` in the application module",
+          type_name::<T>()
+        );
+        (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+      })
+  }
+}
+
+fn read_toml(path: &Path, required: bool) -> Result<Option<Table>, ConfigError> {
+  let content = match fs::read_to_string(path) {
+    Ok(content) => content,
+    Err(err) if err.kind() == io::ErrorKind::NotFound && !required => return Ok(None),
+    Err(err) => return Err(ConfigError::Io(path.to_owned(), err)),
+  };
+
+  content
+    .parse()
+    .map(Some)
+    .map_err(|err| ConfigError::Toml(path.to_owned(), err))
+}
+
+/// `config/app.toml` -> `config/app.production.toml`
+fn with_environment(path: &Path, environment: &str) -> PathBuf {
+  let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+  match path.extension() {
+    Some(ext) => path.with_file_name(format!("{stem}.{environment}.{}", ext.to_string_lossy())),
+    None => path.with_file_name(format!("{stem}.{environment}")),
+  }
+}
+
+/// The variables of a `.env` file, quoted values are strings, e.g. `VERSION="1.0"`.
+fn read_dotenv(path: &Path) -> Result<Vec<(String, Value)>, ConfigError> {
+  let content = match fs::read_to_string(path) {
+    Ok(content) => content,
+    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+    Err(err) => return Err(ConfigError::Io(path.to_owned(), err)),
+  };
+
+  let mut vars = vec![];
+
+  for (i, line) in content.lines().enumerate() {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    let line = line.strip_prefix("export ").unwrap_or(line);
+
+    let Some((key, value)) = line.split_once('=') else {
+      return Err(ConfigError::Dotenv(path.to_owned(), i + 1));
+    };
+
+    let value = value.trim();
+    let value = ['"', '\'']
+      .iter()
+      .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+      .map_or_else(
+        || parse_value(value),
+        |value| Value::String(value.to_owned()),
+      );
+
+    vars.push((key.trim().to_owned(), value));
+  }
+
+  Ok(vars)
+}
+
+/// Sets `DATABASE__URL` as `database.url`.
+fn insert_var(table: &mut Table, key: &str, value: Value) {
+  let mut keys = key.split("__").map(str::to_lowercase).peekable();
+  let mut table = table;
+
+  while let Some(key) = keys.next() {
+    if keys.peek().is_none() {
+      table.insert(key, value);
+      return;
+    }
+
+    let entry = table
+      .entry(key)
+      .or_insert_with(|| Value::Table(Table::new()));
+
+    if !entry.is_table() {
+      *entry = Value::Table(Table::new());
+    }
+
+    let Value::Table(next) = entry else {
+      unreachable!()
+    };
+
+    table = next;
+  }
+}
+
+fn parse_value(value: &str) -> Value {
+  format!("value = {value}")
+    .parse::<Table>()
+    .ok()
+    .and_then(|mut table| table.remove("value"))
+    .unwrap_or_else(|| Value::String(value.to_owned()))
+}
+
+fn merge(table: &mut Table, other: Table) {
+  for (key, value) in other {
+    match (table.get_mut(&key), value) {
+      (Some(Value::Table(existing)), Value::Table(value)) => merge(existing, value),
+      (_, value) => {
+        table.insert(key, value);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use toml::{Table, Value};
+
+  use super::{insert_var, parse_value, read_dotenv, with_environment, ConfigModule};
+  use crate::axum::{IntoRouter, RouterError};
+
+  #[test]
+  fn vars_nested_and_parsed() {
+    let mut table: Table = "[database]\nurl = \"a\"".parse().unwrap();
+
+    for (key, value) in [
+      ("PORT", "8080"),
+      ("DATABASE__POOL_SIZE", "5"),
+      ("NAME", "todo app"),
+      ("VERSION", "'1.0'"),
+    ] {
+      insert_var(&mut table, key, parse_value(value));
+    }
+
+    assert_eq!(table["port"], Value::Integer(8080));
+    assert_eq!(table["database"]["url"], Value::String("a".into()));
+    assert_eq!(table["database"]["pool_size"], Value::Integer(5));
+    assert_eq!(table["name"], Value::String("todo app".into()));
+    assert_eq!(table["version"], Value::String("1.0".into()));
+  }
+
+  #[test]
+  fn quoted_dotenv_values_are_strings() {
+    let path = std::env::temp_dir().join(format!("restify-{}.env", std::process::id()));
+    std::fs::write(&path, "VERSION=\"1.0\"\nRELEASE='2'\nPORT=8080\n").unwrap();
+
+    let vars = read_dotenv(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+      vars,
+      [
+        ("VERSION".into(), Value::String("1.0".into())),
+        ("RELEASE".into(), Value::String("2".into())),
+        ("PORT".into(), Value::Integer(8080)),
+      ]
+    );
+  }
+
+  #[test]
+  fn environment_files() {
+    assert_eq!(
+      with_environment("config/app.toml".as_ref(), "prod"),
+      std::path::Path::new("config/app.prod.toml")
+    );
+  }
+
+  #[test]
+  fn load_error_returned_by_router() {
+    let module = ConfigModule::<Table>::new().file("missing.toml");
+    let err = IntoRouter::<(), ()>::try_into_router(module, &mut ()).unwrap_err();
+
+    assert!(matches!(err, RouterError::Provider(_)), "{err}");
+    assert!(
+      err.to_string().contains("Failed to read `missing.toml`"),
+      "{err}"
+    );
+  }
+}
@@ -0,0 +1,234 @@
+use std::{borrow::Cow, error::Error, fmt};
+
+use axum::http::Method;
+
+use super::RouteMethods;
+use crate::{path::PathError, ProviderError};
+
+/// An error building the router of a module tree.
+#[derive(Debug, Clone)]
+pub enum RouterError {
+  Conflict(RouteConflict),
+  /// A path isn't a valid [`PathTemplate`](crate::path::PathTemplate).
+  InvalidPath {
+    route: Box<RouteSource>,
+    error: PathError,
+  },
+  /// A module failed to build a provider, e.g. the configuration of a `ConfigModule`.
+  Provider(ProviderError),
+}
+
+impl fmt::Display for RouterError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RouterError::Conflict(conflict) => conflict.fmt(f),
+      RouterError::InvalidPath { route, error } => {
+        write!(f, "Invalid path `{}` of {route}: {error}", route.path)
+      }
+      RouterError::Provider(error) => error.fmt(f),
+    }
+  }
+}
+
+impl Error for RouterError {}
+
+impl From<RouteConflict> for RouterError {
+  fn from(conflict: RouteConflict) -> Self {
+    Self::Conflict(conflict)
+  }
+}
+
+/// Two routes of a module tree that axum can't register together: they handle the same method, or
+/// both any method, at the same path, or their paths only differ by the names of their parameters.
+#[derive(Debug, Clone)]
+pub struct RouteConflict {
+  /// The method both routes handle, `None` when both handle any method or when their paths
+  /// conflict whatever their methods.
+  pub method: Option<Method>,
+  pub first: Box<RouteSource>,
+  pub second: Box<RouteSource>,
+}
+
+/// Where a route of a [`RouteConflict`] comes from.
+#[derive(Debug, Clone)]
+pub struct RouteSource {
+  /// The full path of the route, including the controller path and the version prefix.
+  pub path: String,
+  pub controller: Option<Cow<'static, str>>,
+  pub handler: Option<Cow<'static, str>>,
+}
+
+impl fmt::Display for RouteSource {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (&self.controller, &self.handler) {
+      (Some(controller), Some(handler)) => write!(f, "`{controller}::{handler}`"),
+      (Some(controller), None) => write!(f, "`{controller}`"),
+      (None, Some(handler)) => write!(f, "`{handler}`"),
+      (None, None) => f.write_str("an unnamed route"),
+    }
+  }
+}
+
+impl fmt::Display for RouteConflict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let RouteConflict {
+      method,
+      first,
+      second,
+    } = self;
+
+    if first.path != second.path {
+      return write!(
+        f,
+        "Conflicting paths `{}` of {first} and `{}` of {second}, parameters at the same position must have the same name and kind",
+        first.path, second.path
+      );
+    }
+
+    let method = method.as_ref().map_or("Any method", Method::as_str);
+
+    write!(
+      f,
+      "Conflicting routes for `{method} {}`, registered by {first} and {second}",
+      first.path
+    )
+  }
+}
+
+impl Error for RouteConflict {}
+
+/// A route about to be added to the router.
+pub(crate) struct Entry<'a> {
+  pub source: RouteSource,
+  /// The path registered to axum.
+  pub key: String,
+  pub version: Option<&'a str>,
+  pub methods: &'a RouteMethods,
+}
+
+/// Finds the first conflict of `entries`, in registration order. `versioned` is whether routes of
+/// different versions share their path (versioning by header or media type).
+pub(crate) fn find_conflict(entries: &[Entry<'_>], versioned: bool) -> Result<(), RouteConflict> {
+  for (i, second) in entries.iter().enumerate() {
+    for first in &entries[..i] {
+      let conflict = |method| RouteConflict {
+        method,
+        first: Box::new(first.source.clone()),
+        second: Box::new(second.source.clone()),
+      };
+
+      if first.key != second.key {
+        if paths_conflict(&first.key, &second.key) {
+          return Err(conflict(None));
+        }
+
+        continue;
+      }
+
+      if versioned && first.version != second.version {
+        continue;
+      }
+
+      // Routes of any method both take the fallback of the path, while the routes of specific
+      // methods, custom ones included, are merged and take precedence over the fallback. Routes
+      // of unknown methods are left to axum.
+      match (first.methods, second.methods) {
+        (RouteMethods::Any, RouteMethods::Any) => return Err(conflict(None)),
+        (RouteMethods::Only(a), RouteMethods::Only(b)) => {
+          if let Some(method) = a.iter().find(|method| b.contains(method)) {
+            return Err(conflict(Some(method.clone())));
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Whether axum's router rejects two different paths: a parameter and a wildcard, or two
+/// wildcards, at the same position, or paths only differing by the names of their parameters.
+fn paths_conflict(a: &str, b: &str) -> bool {
+  let (a, b): (Vec<_>, Vec<_>) = (a.split('/').collect(), b.split('/').collect());
+
+  let Some(i) = a.iter().zip(&b).position(|(a, b)| a != b) else {
+    return false;
+  };
+
+  let kind = |segment: &str| segment.chars().next().filter(|c| *c == ':' || *c == '*');
+
+  match (kind(a[i]), kind(b[i])) {
+    (Some('*'), Some(_)) | (Some(_), Some('*')) => true,
+    (Some(_), Some(_)) => {
+      a.len() == b.len()
+        && a
+          .iter()
+          .zip(&b)
+          .all(|(a, b)| a == b || (kind(a).is_some() && kind(a) == kind(b)))
+    }
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use axum::http::Method;
+
+  use super::{find_conflict, Entry, RouteSource};
+  use crate::axum::RouteMethods;
+
+  fn entry<'a>(path: &str, methods: &'a RouteMethods) -> Entry<'a> {
+    Entry {
+      key: path.into(),
+      source: RouteSource {
+        path: path.into(),
+        controller: Some("TodoController".into()),
+        handler: None,
+      },
+      version: None,
+      methods,
+    }
+  }
+
+  #[test]
+  fn conflicts_found() {
+    let get = &RouteMethods::Only(vec![Method::GET]);
+    let post = &RouteMethods::Only(vec![Method::POST]);
+    let any = &RouteMethods::Any;
+    let unknown = &RouteMethods::Unknown;
+
+    assert!(find_conflict(&[entry("/todo", get), entry("/todo", post)], false).is_ok());
+    assert!(find_conflict(&[entry("/todo", get), entry("/todo/:id", get)], false).is_ok());
+
+    assert!(find_conflict(&[entry("/todo", post), entry("/todo", any)], false).is_ok());
+
+    let conflict = find_conflict(&[entry("/todo", any), entry("/todo", any)], false).unwrap_err();
+    assert_eq!(conflict.method, None);
+
+    assert!(find_conflict(&[entry("/todo", unknown), entry("/todo", unknown)], false).is_ok());
+    assert!(find_conflict(&[entry("/todo", get), entry("/todo", unknown)], false).is_ok());
+
+    let propfind = &RouteMethods::Only(vec![Method::from_bytes(b"PROPFIND").unwrap()]);
+    let proppatch = &RouteMethods::Only(vec![Method::from_bytes(b"PROPPATCH").unwrap()]);
+
+    assert!(find_conflict(&[entry("/dav", propfind), entry("/dav", proppatch)], false).is_ok());
+
+    let conflict =
+      find_conflict(&[entry("/dav", propfind), entry("/dav", propfind)], false).unwrap_err();
+    assert_eq!(
+      conflict.method.as_ref().map(Method::as_str),
+      Some("PROPFIND")
+    );
+
+    let conflict = find_conflict(
+      &[entry("/todo/:id", get), entry("/todo/:name", post)],
+      false,
+    )
+    .unwrap_err();
+    assert_eq!(conflict.second.path, "/todo/:name");
+
+    assert!(find_conflict(&[entry("/todo/:id", any), entry("/todo/*rest", any)], false).is_err());
+    assert!(find_conflict(&[entry("/todo/new", any), entry("/todo/:id", any)], false).is_ok());
+  }
+}
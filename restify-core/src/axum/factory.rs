@@ -0,0 +1,76 @@
+use std::future::Future;
+
+use axum::{
+  extract::FromRequestParts,
+  http::request::Parts,
+  response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+
+/// An async function building `T` from extractors, the `factory` of
+/// `#[injectable(factory = Self::build)]`:
+///
+/// ```ignore
+/// #[derive(Injectable)]
+/// #[injectable(factory = Self::build)]
+/// struct TenantClient {
+///   client: Client,
+/// }
+///
+/// impl TenantClient {
+///   async fn build(tenant: Tenant, State(state): State<AppState>) -> Result<Self, ApiError> {
+///     Ok(Self { client: state.clients.for_tenant(&tenant).await? })
+///   }
+/// }
+/// ```
+///
+/// The arguments are extracted in order, and the rejections of the arguments and the error of the
+/// factory are turned into responses.
+#[diagnostic::on_unimplemented(
+  message = "`{Self}` is not a factory of `{T}`",
+  note = "A factory is an async function taking up to 12 arguments implementing `FromRequestParts` and returning `Result<{T}, E>` with `E: IntoResponse`",
+  note = "The state is `{S}`, it isn't inferred from the arguments: set it with `#[injectable(state(AppState))]` or `state` in `restify.toml` when an argument needs a specific one, e.g. `State<AppState>`"
+)]
+pub trait InjectableFactory<S, Args, T>: Sized {
+  fn build<'a>(self, parts: &'a mut Parts, state: &'a S) -> BoxFuture<'a, Result<T, Response>>;
+}
+
+macro_rules! impl_injectable_factory {
+  ($($arg:ident),*) => {
+    #[allow(non_snake_case, unused_variables)]
+    impl<F, Fut, S, T, E, $($arg,)*> InjectableFactory<S, ($($arg,)*), T> for F
+    where
+      F: FnOnce($($arg),*) -> Fut + Send + 'static,
+      Fut: Future<Output = Result<T, E>> + Send + 'static,
+      E: IntoResponse,
+      S: Send + Sync,
+      $($arg: FromRequestParts<S> + Send,)*
+    {
+      fn build<'a>(self, parts: &'a mut Parts, state: &'a S) -> BoxFuture<'a, Result<T, Response>> {
+        Box::pin(async move {
+          $(
+            let $arg = $arg::from_request_parts(parts, state)
+              .await
+              .map_err(IntoResponse::into_response)?;
+          )*
+
+          self($($arg),*).await.map_err(IntoResponse::into_response)
+        })
+      }
+    }
+  };
+}
+
+impl_injectable_factory!();
+impl_injectable_factory!(T1);
+impl_injectable_factory!(T1, T2);
+impl_injectable_factory!(T1, T2, T3);
+impl_injectable_factory!(T1, T2, T3, T4);
+impl_injectable_factory!(T1, T2, T3, T4, T5);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6, T7);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_injectable_factory!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
@@ -0,0 +1,402 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fmt,
+  future::Future,
+  marker::PhantomData,
+  pin::Pin,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+  },
+};
+
+use axum::{
+  async_trait,
+  extract::{
+    ws::{Message, WebSocket, WebSocketUpgrade},
+    FromRequestParts,
+  },
+  http::{request::Parts, StatusCode},
+  response::{IntoResponse, Response},
+  routing::{get, MethodRouter},
+  Extension,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc::{channel, Sender as ChannelSender};
+
+use crate::Providers;
+
+/// A message of a gateway in both directions, e.g. `{"event": "todo.create", "data": {..}}`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WsMessage {
+  pub event: String,
+  #[serde(default)]
+  pub data: Value,
+}
+
+impl WsMessage {
+  /// Deserializes the data of the message for a `#[subscribe]` handler.
+  pub fn data<T>(&self) -> Result<T, WsError>
+  where
+    T: DeserializeOwned,
+  {
+    T::deserialize(&self.data).map_err(WsError::InvalidData)
+  }
+}
+
+/// An error handling a message, sent back to the client as an `error` event.
+#[derive(Debug)]
+pub enum WsError {
+  /// No `#[subscribe]` handler for the event.
+  UnknownEvent(String),
+  /// The message or its data can't be deserialized.
+  InvalidData(serde_json::Error),
+  /// The data of an emitted event can't be serialized.
+  Serialize(serde_json::Error),
+}
+
+impl fmt::Display for WsError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      WsError::UnknownEvent(event) => write!(f, "Unknown event `{event}`"),
+      WsError::InvalidData(err) => write!(f, "Invalid data: {err}"),
+      WsError::Serialize(err) => write!(f, "Failed to serialize data: {err}"),
+    }
+  }
+}
+
+impl std::error::Error for WsError {}
+
+/// The reply of a `#[subscribe]` handler, if any.
+pub type WsResult = Result<Option<Value>, WsError>;
+
+pub type BoxWsFuture<'a> = Pin<Box<dyn Future<Output = WsResult> + Send + 'a>>;
+
+/// Dispatches the messages of a connection to the `#[subscribe]` handlers of a gateway.
+///
+/// Implemented by `#[gateway("<path>")]`.
+pub trait GatewayHandler: Send + Sync + 'static {
+  fn handle<'a>(&'a self, client: &'a WsClient, message: &'a WsMessage) -> BoxWsFuture<'a>;
+}
+
+/// Serializes the value returned by a `#[subscribe]` handler, replied to the client under the
+/// event of the message.
+pub fn ws_reply<T>(data: T) -> WsResult
+where
+  T: Serialize,
+{
+  serde_json::to_value(data)
+    .map(Some)
+    .map_err(WsError::Serialize)
+}
+
+/// The messages buffered for a client. A client that doesn't keep up loses the events emitted
+/// while its buffer is full, rather than growing it without limit.
+const BUFFER: usize = 64;
+
+type Sender = ChannelSender<Message>;
+
+/// The clients connected to a gateway and the rooms they joined.
+#[derive(Default, Clone)]
+struct Rooms(Arc<Mutex<RoomsInner>>);
+
+#[derive(Default)]
+struct RoomsInner {
+  clients: HashMap<u64, Sender>,
+  rooms: HashMap<String, HashSet<u64>>,
+}
+
+impl Rooms {
+  /// Emits to the clients of `room`, or of the whole gateway, except `except`.
+  fn emit(&self, room: Option<&str>, except: Option<u64>, message: Message) {
+    let inner = self.0.lock().unwrap();
+
+    let send = |id: &u64, tx: &Sender| {
+      if Some(*id) != except {
+        let _ = tx.try_send(message.clone());
+      }
+    };
+
+    match room {
+      Some(room) => {
+        for id in inner.rooms.get(room).into_iter().flatten() {
+          if let Some(tx) = inner.clients.get(id) {
+            send(id, tx);
+          }
+        }
+      }
+      None => inner.clients.iter().for_each(|(id, tx)| send(id, tx)),
+    }
+  }
+
+  fn connect(&self, id: u64, tx: Sender) {
+    self.0.lock().unwrap().clients.insert(id, tx);
+  }
+
+  fn disconnect(&self, id: u64) {
+    let mut inner = self.0.lock().unwrap();
+
+    inner.clients.remove(&id);
+    inner.rooms.retain(|_, members| {
+      members.remove(&id);
+      !members.is_empty()
+    });
+  }
+}
+
+/// The client of a connection, given to `#[subscribe]` handlers taking a `&WsClient`.
+pub struct WsClient {
+  id: u64,
+  tx: Sender,
+  rooms: Rooms,
+}
+
+impl WsClient {
+  fn new(tx: Sender, rooms: Rooms) -> Self {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    rooms.connect(id, tx.clone());
+
+    Self { id, tx, rooms }
+  }
+
+  /// An ID of the connection, unique in the process.
+  pub fn id(&self) -> u64 {
+    self.id
+  }
+
+  /// Sends an event to this client.
+  pub fn emit<T>(&self, event: &str, data: T) -> Result<(), WsError>
+  where
+    T: Serialize,
+  {
+    let _ = self.tx.try_send(encode(event, data)?);
+    Ok(())
+  }
+
+  pub fn join(&self, room: impl Into<String>) {
+    let mut inner = self.rooms.0.lock().unwrap();
+    inner.rooms.entry(room.into()).or_default().insert(self.id);
+  }
+
+  pub fn leave(&self, room: &str) {
+    let mut inner = self.rooms.0.lock().unwrap();
+
+    if let Some(members) = inner.rooms.get_mut(room) {
+      members.remove(&self.id);
+
+      if members.is_empty() {
+        inner.rooms.remove(room);
+      }
+    }
+  }
+
+  /// Emits to the other clients in `room`.
+  pub fn to(&self, room: impl Into<String>) -> Broadcast<'_> {
+    Broadcast {
+      rooms: &self.rooms,
+      room: Some(room.into()),
+      except: Some(self.id),
+    }
+  }
+
+  /// Emits to all the other clients of the gateway.
+  pub fn broadcast(&self) -> Broadcast<'_> {
+    Broadcast {
+      rooms: &self.rooms,
+      room: None,
+      except: Some(self.id),
+    }
+  }
+}
+
+/// Emits an event to the clients of a room or of the whole gateway, except the sender when
+/// emitted by a [`WsClient`].
+pub struct Broadcast<'a> {
+  rooms: &'a Rooms,
+  room: Option<String>,
+  except: Option<u64>,
+}
+
+impl Broadcast<'_> {
+  pub fn emit<T>(&self, event: &str, data: T) -> Result<(), WsError>
+  where
+    T: Serialize,
+  {
+    let message = encode(event, data)?;
+    self.rooms.emit(self.room.as_deref(), self.except, message);
+    Ok(())
+  }
+}
+
+/// The clients connected to the gateway `G`, to emit to them from anywhere, e.g. from the
+/// handlers of a controller:
+///
+/// ```ignore
+/// async fn create(self, Json(dto): Json<CreateTodoDto>, ws: WsServer<TodoGateway>) {
+///   let todo = self.service.create(dto).await;
+///   ws.to(&todo.list).emit("todo.created", &todo)?;
+/// }
+/// ```
+///
+/// Registered as a provider by the modules listing `G` in their `gateways`.
+pub struct WsServer<G> {
+  rooms: Rooms,
+  gateway: PhantomData<fn() -> G>,
+}
+
+impl<G> WsServer<G> {
+  pub fn new() -> Self {
+    Self {
+      rooms: Rooms::default(),
+      gateway: PhantomData,
+    }
+  }
+
+  /// Emits to the clients in `room`.
+  pub fn to(&self, room: impl Into<String>) -> Broadcast<'_> {
+    Broadcast {
+      rooms: &self.rooms,
+      room: Some(room.into()),
+      except: None,
+    }
+  }
+
+  /// Emits to all the clients of the gateway.
+  pub fn broadcast(&self) -> Broadcast<'_> {
+    Broadcast {
+      rooms: &self.rooms,
+      room: None,
+      except: None,
+    }
+  }
+}
+
+impl<G> Default for WsServer<G> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<G> Clone for WsServer<G> {
+  fn clone(&self) -> Self {
+    Self {
+      rooms: self.rooms.clone(),
+      gateway: PhantomData,
+    }
+  }
+}
+
+#[async_trait]
+impl<G, S> FromRequestParts<S> for WsServer<G>
+where
+  G: 'static,
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    let server = parts
+      .extensions
+      .get::<Providers>()
+      .and_then(|providers| providers.get::<Self>());
+
+    server.map(|server| (*server).clone()).ok_or_else(|| {
+      let msg = format!(
+        "Missing gateway `{}`, is it in the `gateways` of a module?",
+        std::any::type_name::<G>()
+      );
+      (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+    })
+  }
+}
+
+fn encode<T>(event: &str, data: T) -> Result<Message, WsError>
+where
+  T: Serialize,
+{
+  let message = WsMessage {
+    event: event.to_owned(),
+    data: serde_json::to_value(data).map_err(WsError::Serialize)?,
+  };
+
+  Ok(Message::Text(
+    serde_json::to_string(&message).map_err(WsError::Serialize)?,
+  ))
+}
+
+/// The route of a gateway: upgrades the connection, extracts the gateway (like a controller) and
+/// dispatches the text messages to it, one at a time.
+///
+/// The clients join the rooms of the [`WsServer`] provided by the modules, or of the route when
+/// the gateway isn't in the `gateways` of a module.
+pub fn gateway_route<G, S>() -> MethodRouter<S>
+where
+  G: GatewayHandler + FromRequestParts<S>,
+  S: Clone + Send + Sync + 'static,
+{
+  let fallback = WsServer::<G>::new();
+
+  get(
+    move |providers: Option<Extension<Providers>>, ws: WebSocketUpgrade, gateway: G| {
+      let rooms = providers
+        .and_then(|Extension(providers)| providers.get::<WsServer<G>>())
+        .map_or_else(|| fallback.rooms.clone(), |server| server.rooms.clone());
+
+      async move { ws.on_upgrade(move |socket| serve(gateway, socket, rooms)) }
+    },
+  )
+}
+
+async fn serve<G>(gateway: G, socket: WebSocket, rooms: Rooms)
+where
+  G: GatewayHandler,
+{
+  let (mut sink, mut stream) = socket.split();
+  let (tx, mut rx) = channel(BUFFER);
+
+  let writer = tokio::spawn(async move {
+    while let Some(message) = rx.recv().await {
+      if sink.send(message).await.is_err() {
+        break;
+      }
+    }
+  });
+
+  let client = WsClient::new(tx, rooms);
+
+  while let Some(Ok(message)) = stream.next().await {
+    let text = match message {
+      Message::Text(text) => text,
+      Message::Close(_) => break,
+      _ => continue,
+    };
+
+    match serde_json::from_str::<WsMessage>(&text) {
+      Ok(message) => {
+        let res = gateway
+          .handle(&client, &message)
+          .await
+          .and_then(|reply| reply.map_or(Ok(()), |reply| client.emit(&message.event, reply)));
+
+        if let Err(err) = res {
+          emit_error(&client, Some(&message.event), err);
+        }
+      }
+      Err(err) => emit_error(&client, None, WsError::InvalidData(err)),
+    }
+  }
+
+  client.rooms.disconnect(client.id);
+  drop(client);
+
+  let _ = writer.await;
+}
+
+fn emit_error(client: &WsClient, event: Option<&str>, err: WsError) {
+  let data = serde_json::json!({ "event": event, "message": err.to_string() });
+  let _ = client.emit("error", data);
+}
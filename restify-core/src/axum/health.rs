@@ -0,0 +1,365 @@
+use std::{
+  borrow::Cow, collections::BTreeMap, fmt::Display, future::Future, marker::PhantomData, sync::Arc,
+};
+
+use axum::{
+  async_trait,
+  extract::State,
+  http::{header::CACHE_CONTROL, HeaderValue, Method, StatusCode},
+  response::{IntoResponse, Response},
+  routing::get,
+  Extension, Json,
+};
+use futures_util::future::join_all;
+use serde::Serialize;
+
+use super::{Route, Routes};
+use crate::{ControllerDetails, Module, ModuleDetails, Providers};
+
+/// The status of a [`HealthIndicator`] or of the whole service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+  Up,
+  Down,
+}
+
+/// The result of a health check, with optional details, e.g. the latency of a database ping.
+#[derive(Debug, Clone, Serialize)]
+pub struct Health {
+  pub status: HealthStatus,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub details: Option<serde_json::Value>,
+}
+
+impl Health {
+  pub fn up() -> Self {
+    Self {
+      status: HealthStatus::Up,
+      details: None,
+    }
+  }
+
+  pub fn down() -> Self {
+    Self {
+      status: HealthStatus::Down,
+      details: None,
+    }
+  }
+
+  /// Up if `res` is `Ok`, otherwise down with the error in the details.
+  pub fn from_result<T, E>(res: Result<T, E>) -> Self
+  where
+    E: Display,
+  {
+    match res {
+      Ok(_) => Self::up(),
+      Err(err) => Self::down().details(serde_json::json!({ "error": err.to_string() })),
+    }
+  }
+
+  pub fn details(mut self, details: impl Serialize) -> Self {
+    self.details = serde_json::to_value(details).ok();
+    self
+  }
+}
+
+/// A check reported by [`HealthModule`], e.g. a database ping or the free disk space.
+#[async_trait]
+pub trait HealthIndicator<S = ()>: Send + Sync + 'static {
+  /// The key of the indicator in the report.
+  fn name(&self) -> Cow<'static, str>;
+
+  async fn check(&self, state: &S) -> Health;
+}
+
+struct FnIndicator<F> {
+  name: Cow<'static, str>,
+  check: F,
+}
+
+#[async_trait]
+impl<S, F, Fut> HealthIndicator<S> for FnIndicator<F>
+where
+  S: Clone + Send + Sync + 'static,
+  F: Fn(S) -> Fut + Send + Sync + 'static,
+  Fut: Future<Output = Health> + Send,
+{
+  fn name(&self) -> Cow<'static, str> {
+    self.name.clone()
+  }
+
+  async fn check(&self, state: &S) -> Health {
+    (self.check)(state.clone()).await
+  }
+}
+
+type Indicators<S> = Vec<Arc<dyn HealthIndicator<S>>>;
+
+/// An indicator registered by a module in its providers, checked by the [`HealthModule`] of the
+/// application along with its own:
+///
+/// ```ignore
+/// providers.add(RegisteredIndicator::readiness(DatabaseIndicator));
+/// ```
+///
+/// A derived module registers readiness indicators with `#[module(health(DatabaseIndicator))]`.
+pub struct RegisteredIndicator<S = ()> {
+  indicator: Arc<dyn HealthIndicator<S>>,
+  liveness: bool,
+}
+
+impl<S> RegisteredIndicator<S>
+where
+  S: 'static,
+{
+  pub fn readiness(indicator: impl HealthIndicator<S>) -> Self {
+    Self {
+      indicator: Arc::new(indicator),
+      liveness: false,
+    }
+  }
+
+  pub fn liveness(indicator: impl HealthIndicator<S>) -> Self {
+    Self {
+      indicator: Arc::new(indicator),
+      liveness: true,
+    }
+  }
+}
+
+/// A module exposing `<path>/live` and `<path>/ready` (`/health` by default), answering
+/// `200 OK` when all their indicators are up and `503 Service Unavailable` otherwise:
+///
+/// ```json
+/// {"status": "down", "checks": {"db": {"status": "down", "details": {"error": "timed out"}}}}
+/// ```
+///
+/// Liveness indicators are checked by both endpoints, the other ones only by `ready`. The
+/// [`RegisteredIndicator`]s of the other modules are checked too.
+pub struct HealthModule<S = (), Ctx = ()> {
+  path: Cow<'static, str>,
+  liveness: Indicators<S>,
+  readiness: Indicators<S>,
+  _marker: PhantomData<fn() -> Ctx>,
+}
+
+impl<S, Ctx> HealthModule<S, Ctx>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  pub fn new() -> Self {
+    Self {
+      path: "/health".into(),
+      liveness: vec![],
+      readiness: vec![],
+      _marker: PhantomData,
+    }
+  }
+
+  pub fn path(mut self, path: impl Into<Cow<'static, str>>) -> Self {
+    self.path = path.into();
+    self
+  }
+
+  /// Adds a readiness indicator.
+  pub fn indicator(mut self, indicator: impl HealthIndicator<S>) -> Self {
+    self.readiness.push(Arc::new(indicator));
+    self
+  }
+
+  /// Adds a liveness indicator, a failure there means the service must be restarted.
+  pub fn liveness(mut self, indicator: impl HealthIndicator<S>) -> Self {
+    self.liveness.push(Arc::new(indicator));
+    self
+  }
+
+  /// Adds a readiness indicator from an async function of the state.
+  pub fn check<F, Fut>(self, name: impl Into<Cow<'static, str>>, check: F) -> Self
+  where
+    F: Fn(S) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Health> + Send,
+  {
+    self.indicator(FnIndicator {
+      name: name.into(),
+      check,
+    })
+  }
+}
+
+impl<S, Ctx> Default for HealthModule<S, Ctx>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<S, Ctx> Module for HealthModule<S, Ctx>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  type Context = Ctx;
+  type ControllerContext = ();
+  type ControllerReturn = Routes<S>;
+
+  fn details(&self, _ctx: &mut Ctx) -> ModuleDetails<Ctx, (), Routes<S>> {
+    let path = self.path.clone();
+    let live = self.liveness.clone();
+    let ready: Indicators<S> = self
+      .liveness
+      .iter()
+      .chain(&self.readiness)
+      .cloned()
+      .collect();
+
+    ModuleDetails {
+      imports: vec![],
+      controllers: vec![Box::new(move |_| ControllerDetails {
+        path: path.clone(),
+        return_: Routes::new()
+          .route(Route::new("/live", endpoint(live.clone(), true)).methods(&[Method::GET]))
+          .route(Route::new("/ready", endpoint(ready.clone(), false)).methods(&[Method::GET]))
+          .controller("HealthModule"),
+      })],
+      gateways: vec![],
+      providers: Providers::new(),
+    }
+  }
+}
+
+/// Checks `indicators` and the ones registered by the modules, only their liveness ones if `live`.
+fn endpoint<S>(indicators: Indicators<S>, live: bool) -> axum::routing::MethodRouter<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  get(
+    move |State(state): State<S>, providers: Option<Extension<Providers>>| {
+      let mut indicators = indicators.clone();
+
+      if let Some(Extension(providers)) = providers {
+        indicators.extend(
+          providers
+            .all::<RegisteredIndicator<S>>()
+            .filter(|registered| registered.liveness || !live)
+            .map(|registered| registered.indicator.clone()),
+        );
+      }
+
+      async move { report(&indicators, &state).await }
+    },
+  )
+}
+
+#[derive(Serialize)]
+struct Report {
+  status: HealthStatus,
+  checks: BTreeMap<Cow<'static, str>, Health>,
+}
+
+async fn report<S>(indicators: &[Arc<dyn HealthIndicator<S>>], state: &S) -> Response
+where
+  S: 'static,
+{
+  let checks = join_all(
+    indicators
+      .iter()
+      .map(|indicator| async move { (indicator.name(), indicator.check(state).await) }),
+  )
+  .await;
+
+  let (status, code) = if checks.iter().all(|(_, h)| h.status == HealthStatus::Up) {
+    (HealthStatus::Up, StatusCode::OK)
+  } else {
+    (HealthStatus::Down, StatusCode::SERVICE_UNAVAILABLE)
+  };
+
+  let report = Report {
+    status,
+    checks: checks.into_iter().collect(),
+  };
+
+  (
+    code,
+    [(CACHE_CONTROL, HeaderValue::from_static("no-store"))],
+    Json(report),
+  )
+    .into_response()
+}
+
+#[cfg(test)]
+mod test {
+  use axum::{body::Body, extract::Request, http::StatusCode};
+
+  use super::{FnIndicator, Health, HealthModule, RegisteredIndicator};
+  use crate::{
+    axum::{test::send, IntoRouter, Routes},
+    BoxedModule, Module, ModuleDetails, Providers,
+  };
+
+  /// A module registering a down readiness indicator and an up liveness one.
+  struct Database;
+
+  impl Module for Database {
+    type Context = ();
+    type ControllerContext = ();
+    type ControllerReturn = Routes;
+
+    fn details(&self, _ctx: &mut ()) -> ModuleDetails<(), (), Routes> {
+      let mut providers = Providers::new();
+      providers.add(RegisteredIndicator::readiness(FnIndicator {
+        name: "db".into(),
+        check: |_: ()| async { Health::down() },
+      }));
+      providers.add(RegisteredIndicator::liveness(FnIndicator {
+        name: "pool".into(),
+        check: |_: ()| async { Health::up() },
+      }));
+
+      ModuleDetails {
+        imports: vec![],
+        controllers: vec![],
+        gateways: vec![],
+        providers,
+      }
+    }
+  }
+
+  struct App;
+
+  impl Module for App {
+    type Context = ();
+    type ControllerContext = ();
+    type ControllerReturn = Routes;
+
+    fn details(&self, _ctx: &mut ()) -> ModuleDetails<(), (), Routes> {
+      let imports: Vec<BoxedModule<(), (), Routes>> =
+        vec![Box::new(HealthModule::<(), ()>::new()), Box::new(Database)];
+
+      ModuleDetails {
+        imports,
+        controllers: vec![],
+        gateways: vec![],
+        providers: Providers::new(),
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn indicators_registered_by_modules() {
+    let router = App.into_router(&mut ());
+    let request = |uri| Request::get(uri).body(Body::empty()).unwrap();
+
+    let (status, body) = send(&router, request("/health/live")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"{"status":"up","checks":{"pool":{"status":"up"}}}"#);
+
+    let (status, body) = send(&router, request("/health/ready")).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+      body,
+      r#"{"status":"down","checks":{"db":{"status":"down"},"pool":{"status":"up"}}}"#
+    );
+  }
+}
@@ -0,0 +1,99 @@
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+  async_trait,
+  extract::FromRequestParts,
+  http::request::Parts,
+  response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use tokio::sync::OnceCell;
+
+type Resolver<T> = Arc<dyn Fn() -> BoxFuture<'static, Result<T, Response>> + Send + Sync>;
+
+/// An injectable extracted on first use instead of with the injectable depending on it, e.g. a
+/// rarely used dependency that is expensive to extract:
+///
+/// ```ignore
+/// #[derive(Injectable)]
+/// struct TodoService {
+///   repository: TodoRepository,
+///   mailer: Lazy<Mailer>,
+/// }
+///
+/// impl TodoService {
+///   async fn share(&self, id: u32, email: &str) -> Result<(), Response> {
+///     self.mailer.get().await?.send(email, &self.repository.get(id)).await;
+///     Ok(())
+///   }
+/// }
+/// ```
+///
+/// `T` is extracted from a copy of the request parts and the state taken at extraction time, so it
+/// sees the injectables of the request built before, but not the ones built after.
+pub struct Lazy<T> {
+  cell: OnceCell<T>,
+  resolve: Resolver<T>,
+}
+
+impl<T> Lazy<T> {
+  /// Extracts `T` on the first call, the following calls return the same instance. A rejection
+  /// isn't kept, the next call extracts `T` again.
+  pub async fn get(&self) -> Result<&T, Response> {
+    self.cell.get_or_try_init(|| (self.resolve)()).await
+  }
+
+  /// The instance extracted by [`Lazy::get`], or a new one.
+  pub async fn into_inner(self) -> Result<T, Response> {
+    match self.cell.into_inner() {
+      Some(value) => Ok(value),
+      None => (self.resolve)().await,
+    }
+  }
+
+  pub fn is_resolved(&self) -> bool {
+    self.cell.initialized()
+  }
+}
+
+impl<T> Clone for Lazy<T>
+where
+  T: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      cell: self.cell.clone(),
+      resolve: self.resolve.clone(),
+    }
+  }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Lazy<T>
+where
+  T: FromRequestParts<S> + Send + 'static,
+  S: Clone + Send + Sync + 'static,
+{
+  type Rejection = Infallible;
+
+  async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+    let parts = parts.clone();
+    let state = state.clone();
+
+    let resolve: Resolver<T> = Arc::new(move || {
+      let mut parts = parts.clone();
+      let state = state.clone();
+
+      Box::pin(async move {
+        T::from_request_parts(&mut parts, &state)
+          .await
+          .map_err(IntoResponse::into_response)
+      })
+    });
+
+    Ok(Self {
+      cell: OnceCell::new(),
+      resolve,
+    })
+  }
+}
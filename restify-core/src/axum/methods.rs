@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use axum::{
+  extract::{Request, State},
+  handler::Handler,
+  http::{header::ALLOW, HeaderValue, Method, StatusCode},
+  middleware::{from_fn, Next},
+  response::IntoResponse,
+  routing::{any, on, MethodFilter, MethodRouter},
+};
+use tower::ServiceExt;
+
+use super::{Route, RouteMethods};
+
+/// Routes requests made with any of `methods` to `handler`.
+///
+/// Methods that `MethodFilter` can't express (`CONNECT` or extension methods like WebDAV's
+/// `PROPFIND`) are matched by routing every method to `handler` and answering the other ones with
+/// `405 Method Not Allowed`. That takes the fallback of the method router, so `IntoRouter` groups
+/// such routes of the same path with [`merge`].
+pub fn on_methods<H, T, S>(methods: &[Method], handler: H) -> MethodRouter<S>
+where
+  H: Handler<T, S>,
+  T: 'static,
+  S: Clone + Send + Sync + 'static,
+{
+  if let Some(filter) = method_filter(methods) {
+    return on(filter, handler);
+  }
+
+  let allow = allow(methods);
+  let methods: Arc<[Method]> = methods.into();
+
+  any(handler).layer(from_fn(move |req: Request, next: Next| {
+    let allowed = methods.contains(req.method());
+    let allow = allow.clone();
+
+    async move {
+      if allowed {
+        next.run(req).await
+      } else {
+        (StatusCode::METHOD_NOT_ALLOWED, [(ALLOW, allow)]).into_response()
+      }
+    }
+  }))
+}
+
+/// The filter of `methods`, `None` when one of them can't be expressed by `MethodFilter`.
+fn method_filter(methods: &[Method]) -> Option<MethodFilter> {
+  methods
+    .iter()
+    .cloned()
+    .map(MethodFilter::try_from)
+    .reduce(|a, b| Ok(a?.or(b?)))?
+    .ok()
+}
+
+fn allow(methods: &[Method]) -> HeaderValue {
+  let allow = methods
+    .iter()
+    .map(Method::as_str)
+    .collect::<Vec<_>>()
+    .join(", ");
+
+  HeaderValue::from_str(&allow).expect("methods are valid header values")
+}
+
+/// Merges the method routers of the routes sharing a path.
+///
+/// Routes of methods `MethodFilter` can't express and routes of any method both take the fallback
+/// of their method router, and axum can't merge two fallbacks. When there are several of them,
+/// they are grouped in a single fallback dispatching on the method: the routes of the method
+/// first, then the route of any method. Other methods are answered with `405 Method Not Allowed`.
+pub(crate) fn merge<S>(routes: Vec<Route<S>>) -> MethodRouter<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  let mut merged = MethodRouter::new();
+  let mut fallbacks = vec![];
+  let mut all_methods = vec![];
+
+  for Route {
+    methods,
+    method_router,
+    ..
+  } in routes
+  {
+    match methods {
+      RouteMethods::Only(methods) if method_filter(&methods).is_none() => {
+        all_methods.extend(methods.iter().cloned());
+        fallbacks.push((Some(methods), method_router));
+      }
+      RouteMethods::Only(methods) => {
+        all_methods.extend(methods);
+        merged = merged.merge(method_router);
+      }
+      RouteMethods::Any => fallbacks.push((None, method_router)),
+      RouteMethods::Unknown => merged = merged.merge(method_router),
+    }
+  }
+
+  if fallbacks.len() < 2 {
+    return fallbacks
+      .into_iter()
+      .fold(merged, |merged, (_, method_router)| {
+        merged.merge(method_router)
+      });
+  }
+
+  let allow = allow(&all_methods);
+  let fallbacks = Arc::new(fallbacks);
+
+  merged.merge(any(move |State(state): State<S>, req: Request| {
+    let fallbacks = fallbacks.clone();
+    let allow = allow.clone();
+
+    async move {
+      let method_router = fallbacks
+        .iter()
+        .find(|(methods, _)| {
+          methods
+            .as_ref()
+            .is_some_and(|methods| methods.contains(req.method()))
+        })
+        .or_else(|| fallbacks.iter().find(|(methods, _)| methods.is_none()))
+        .map(|(_, method_router)| method_router.clone());
+
+      match method_router {
+        Some(method_router) => match method_router.with_state(state).oneshot(req).await {
+          Ok(res) => res,
+          Err(err) => match err {},
+        },
+        None => (StatusCode::METHOD_NOT_ALLOWED, [(ALLOW, allow)]).into_response(),
+      }
+    }
+  }))
+}
+
+#[cfg(test)]
+mod test {
+  use axum::{
+    body::Body,
+    extract::Request,
+    http::{Method, StatusCode},
+    routing::{any, get, post},
+  };
+
+  use super::on_methods;
+  use crate::axum::{
+    test::{send, Routed},
+    IntoRouter, Route, RouterError, Routes,
+  };
+
+  fn webdav_routes() -> Routes {
+    let route = |method: &str, body: &'static str| {
+      let methods = [Method::from_bytes(method.as_bytes()).unwrap()];
+      Route::new("/x", on_methods(&methods, move || async move { body })).methods(&methods)
+    };
+
+    Routes::new()
+      .route(route("PROPFIND", "propfind"))
+      .route(route("PROPPATCH", "proppatch"))
+      .route(route("GET", "get"))
+  }
+
+  #[tokio::test]
+  async fn custom_methods_share_a_path() {
+    let router = Routed(webdav_routes).try_into_router(&mut ()).unwrap();
+    let request = |method: &[u8]| {
+      Request::builder()
+        .method(Method::from_bytes(method).unwrap())
+        .uri("/x")
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    for method in ["PROPFIND", "PROPPATCH", "GET"] {
+      assert_eq!(
+        send(&router, request(method.as_bytes())).await,
+        (StatusCode::OK, method.to_ascii_lowercase())
+      );
+    }
+
+    let (status, _) = send(&router, request(b"DELETE")).await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+  }
+
+  #[tokio::test]
+  async fn routes_of_unknown_methods_merged() {
+    fn routes() -> Routes {
+      Routes::new()
+        .route(Route::new("/x", get(|| async { "get" })))
+        .route(Route::new("/x", post(|| async { "post" })))
+    }
+
+    let router = Routed(routes).try_into_router(&mut ()).unwrap();
+    let request = |method| {
+      Request::builder()
+        .method(method)
+        .uri("/x")
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    assert_eq!(
+      send(&router, request(Method::GET)).await,
+      (StatusCode::OK, "get".into())
+    );
+    assert_eq!(
+      send(&router, request(Method::POST)).await,
+      (StatusCode::OK, "post".into())
+    );
+  }
+
+  #[tokio::test]
+  async fn any_route_after_specific_methods() {
+    fn routes() -> Routes {
+      Routes::new()
+        .route(Route::new("/x", any(|| async { "any" })).any_method())
+        .route(Route::new("/x", get(|| async { "get" })).methods(&[Method::GET]))
+    }
+
+    let router = Routed(routes).try_into_router(&mut ()).unwrap();
+    let request = |method| {
+      Request::builder()
+        .method(method)
+        .uri("/x")
+        .body(Body::empty())
+        .unwrap()
+    };
+
+    assert_eq!(
+      send(&router, request(Method::GET)).await,
+      (StatusCode::OK, "get".into())
+    );
+    assert_eq!(
+      send(&router, request(Method::POST)).await,
+      (StatusCode::OK, "any".into())
+    );
+
+    fn twice() -> Routes {
+      Routes::new()
+        .route(Route::new("/x", any(|| async { "a" })).any_method())
+        .route(Route::new("/x", any(|| async { "b" })).any_method())
+    }
+
+    assert!(matches!(
+      Routed(twice).try_into_router(&mut ()),
+      Err(RouterError::Conflict(_))
+    ));
+  }
+}
@@ -1,26 +1,412 @@
-use crate::{module::resolve_module, Module};
+#[cfg(feature = "config")]
+mod config;
+mod conflict;
+mod factory;
+#[cfg(feature = "ws")]
+mod gateway;
+mod health;
+mod lazy;
+mod methods;
+mod overrides;
+mod response;
+mod routes;
+mod scope;
+#[cfg(feature = "serve-static")]
+mod serve_static;
+mod singleton;
+mod sse;
+mod versioning;
+mod views;
+
+use std::collections::BTreeMap;
+
+use std::{borrow::Cow, iter, sync::Arc};
+
+use axum::{
+  extract::{RawPathParams, Request},
+  http::StatusCode,
+  middleware::{from_fn, Next},
+  response::IntoResponse,
+  routing::MethodRouter,
+  Extension,
+};
+
+use crate::{
+  module::resolve_module,
+  path::{join_paths, ParamKind, PathTemplate},
+  Module,
+};
+
+#[cfg(feature = "config")]
+pub use config::{Config, ConfigError, ConfigModule};
+pub use conflict::{RouteConflict, RouteSource, RouterError};
+pub use factory::InjectableFactory;
+#[cfg(feature = "ws")]
+pub use gateway::{
+  gateway_route, ws_reply, BoxWsFuture, Broadcast, GatewayHandler, WsClient, WsError, WsMessage,
+  WsResult, WsServer,
+};
+pub use health::{Health, HealthIndicator, HealthModule, HealthStatus, RegisteredIndicator};
+pub use lazy::Lazy;
+pub use methods::on_methods;
+pub use overrides::Overrides;
+pub use response::shape_response;
+pub use routes::{Route, RouteMethods, Routes};
+pub use scope::{CachedInScope, NotCachedInScope, Scoped};
+#[cfg(feature = "serve-static")]
+pub use serve_static::ServeStaticModule;
+pub use singleton::{FromProviders, Singleton, SingletonCell, ViaProviders, ViaState};
+pub use sse::{into_sse, IntoSseEvent, LastEventId, SseEvent};
+pub use versioning::{RouterOptions, Versioning};
+pub use views::{RenderError, RenderFailure, Template, TemplateEngine, Views};
+
+#[cfg(feature = "minijinja")]
+pub use views::MiniJinja;
+
+use conflict::{find_conflict, Entry};
+use versioning::{dispatch, uri_prefix, VersionedRoutes};
 
 pub type Router<S = ()> = axum::Router<S>;
 
 pub trait IntoRouter<S, Ctx> {
+  /// Builds the router of the module tree.
+  ///
+  /// # Panics
+  ///
+  /// If a provider can't be built, a path is invalid or two routes conflict, see
+  /// [`try_into_router`](Self::try_into_router).
   fn into_router(self, ctx: &mut Ctx) -> Router<S>;
+
+  fn into_router_with(self, ctx: &mut Ctx, options: RouterOptions) -> Router<S>;
+
+  /// Builds the router of the module tree, or returns the first provider a module failed to build,
+  /// the first invalid path or the first two routes that axum can't register together.
+  fn try_into_router(self, ctx: &mut Ctx) -> Result<Router<S>, RouterError>;
+
+  fn try_into_router_with(
+    self,
+    ctx: &mut Ctx,
+    options: RouterOptions,
+  ) -> Result<Router<S>, RouterError>;
 }
 
 impl<T, S, Ctx> IntoRouter<S, Ctx> for T
 where
   S: Clone + Send + Sync + 'static,
-  T: Module<Context = Ctx, ControllerContext = (), ControllerReturn = Router<S>>,
+  T: Module<Context = Ctx, ControllerContext = (), ControllerReturn = Routes<S>>,
 {
   fn into_router(self, ctx: &mut Ctx) -> Router<S> {
-    let controllers = resolve_module(&self, ctx);
-    let mut router = Router::new();
+    self.into_router_with(ctx, RouterOptions::default())
+  }
+
+  fn into_router_with(self, ctx: &mut Ctx, options: RouterOptions) -> Router<S> {
+    self
+      .try_into_router_with(ctx, options)
+      .unwrap_or_else(|err| panic!("{err}"))
+  }
+
+  fn try_into_router(self, ctx: &mut Ctx) -> Result<Router<S>, RouterError> {
+    self.try_into_router_with(ctx, RouterOptions::default())
+  }
+
+  fn try_into_router_with(
+    self,
+    ctx: &mut Ctx,
+    options: RouterOptions,
+  ) -> Result<Router<S>, RouterError> {
+    let (controllers, providers) = resolve_module(&self, ctx);
+
+    if let Some(error) = providers.errors().first() {
+      return Err(RouterError::Provider(error.clone()));
+    }
+
+    let mut routes = vec![];
 
     for con in controllers {
       let details = con(&mut ());
 
-      router = router.nest(&details.path, details.return_);
+      for route in details.return_.routes {
+        let version = route
+          .version
+          .clone()
+          .or_else(|| options.default_version.clone());
+
+        let path = join_paths(&details.path, &route.path);
+        let template = PathTemplate::parse(&path).map_err(|error| RouterError::InvalidPath {
+          route: Box::new(RouteSource {
+            path,
+            controller: route.controller.clone(),
+            handler: route.handler.clone(),
+          }),
+          error,
+        })?;
+
+        routes.push((template, version, route));
+      }
     }
 
-    router
+    let versioned =
+      |path: String, version: &Option<Cow<'static, str>>| match (&options.versioning, version) {
+        (Versioning::Uri { prefix }, Some(version)) => {
+          join_paths(&uri_prefix(prefix, version), &path)
+        }
+        _ => path,
+      };
+
+    // With URI versioning, the routes of the default version are also mounted without a prefix, so
+    // setting a default version doesn't move the routes of existing clients.
+    let is_default = |version: &Option<Cow<'static, str>>| {
+      matches!(options.versioning, Versioning::Uri { .. })
+        && version.is_some()
+        && *version == options.default_version
+    };
+
+    let entries: Vec<_> = routes
+      .iter()
+      .flat_map(|(template, version, route)| {
+        let entry = |path: String, key: String| Entry {
+          source: RouteSource {
+            path,
+            controller: route.controller.clone(),
+            handler: route.handler.clone(),
+          },
+          key,
+          version: version.as_deref(),
+          methods: &route.methods,
+        };
+
+        let prefixed = entry(
+          versioned(template.to_string(), version),
+          versioned(template.to_axum(), version),
+        );
+        let bare = is_default(version).then(|| entry(template.to_string(), template.to_axum()));
+
+        iter::once(prefixed).chain(bare)
+      })
+      .collect();
+
+    find_conflict(
+      &entries,
+      !matches!(options.versioning, Versioning::Uri { .. }),
+    )?;
+
+    let mut table = BTreeMap::<String, Vec<_>>::new();
+
+    for (template, version, route) in routes {
+      let route = Route {
+        version,
+        method_router: constrain(route.method_router, &template),
+        ..route
+      };
+
+      table.entry(template.to_axum()).or_default().push(route);
+    }
+
+    let mut router = Router::new();
+
+    for (path, routes) in table {
+      let routes = merge_by_version(routes);
+
+      match &options.versioning {
+        Versioning::Uri { prefix } => {
+          for (version, method_router) in routes {
+            if is_default(&version) {
+              router = router.route(&path, method_router.clone());
+            }
+
+            let path = match version {
+              Some(version) => join_paths(&uri_prefix(prefix, &version), &path),
+              None => path.clone(),
+            };
+
+            router = router.route(&path, method_router);
+          }
+        }
+        Versioning::Header { .. } | Versioning::MediaType { .. } => {
+          router = match &routes[..] {
+            [(None, _)] => router.route(&path, routes.into_iter().next().unwrap().1),
+            _ => router.route(
+              &path,
+              dispatch(
+                options.versioning.clone(),
+                options.default_version.clone(),
+                routes,
+              ),
+            ),
+          };
+        }
+      }
+    }
+
+    if providers.is_empty() {
+      return Ok(router);
+    }
+
+    Ok(router.layer(Extension(providers)))
+  }
+}
+
+/// Answers `404 Not Found` to the requests whose typed parameters, like `{id:uuid}`, don't match
+/// their type.
+fn constrain<S>(method_router: MethodRouter<S>, template: &PathTemplate) -> MethodRouter<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  let typed: Arc<[(String, ParamKind)]> = template
+    .params()
+    .filter(|(_, kind)| *kind != ParamKind::String)
+    .map(|(name, kind)| (name.to_owned(), kind))
+    .collect();
+
+  if typed.is_empty() {
+    return method_router;
+  }
+
+  method_router.layer(from_fn(
+    move |params: RawPathParams, req: Request, next: Next| {
+      let matches = params.iter().all(|(name, value)| {
+        !typed
+          .iter()
+          .any(|(typed, kind)| typed == name && !kind.matches(value))
+      });
+
+      async move {
+        if matches {
+          next.run(req).await
+        } else {
+          StatusCode::NOT_FOUND.into_response()
+        }
+      }
+    },
+  ))
+}
+
+/// Merges the routes of a path by version, with [`methods::merge`].
+fn merge_by_version<S>(routes: Vec<Route<S>>) -> VersionedRoutes<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  let mut grouped: Vec<Vec<Route<S>>> = vec![];
+
+  for route in routes {
+    match grouped
+      .iter_mut()
+      .find(|routes| routes[0].version == route.version)
+    {
+      Some(routes) => routes.push(route),
+      None => grouped.push(vec![route]),
+    }
+  }
+
+  grouped
+    .into_iter()
+    .map(|routes| (routes[0].version.clone(), methods::merge(routes)))
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{HeaderName, StatusCode},
+    routing::get,
+  };
+  use tower::ServiceExt;
+
+  use super::{IntoRouter, Route, RouterOptions, Routes, Versioning};
+  use crate::{ControllerDetails, Module, ModuleDetails, Providers};
+
+  /// A module with a single controller at `/`.
+  pub(super) struct Routed(pub fn() -> Routes);
+
+  impl Module for Routed {
+    type Context = ();
+    type ControllerContext = ();
+    type ControllerReturn = Routes;
+
+    fn details(&self, _ctx: &mut ()) -> ModuleDetails<(), (), Routes> {
+      let routes = self.0;
+
+      ModuleDetails {
+        imports: vec![],
+        controllers: vec![Box::new(move |_| ControllerDetails {
+          path: "/".into(),
+          return_: routes(),
+        })],
+        gateways: vec![],
+        providers: Providers::new(),
+      }
+    }
+  }
+
+  pub(super) async fn send(router: &super::Router, request: Request) -> (StatusCode, String) {
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, String::from_utf8_lossy(&body).into_owned())
+  }
+
+  fn get_request(uri: &str, version: Option<&str>) -> Request {
+    let mut builder = Request::get(uri);
+
+    if let Some(version) = version {
+      builder = builder.header("x-api-version", version);
+    }
+
+    builder.body(Body::empty()).unwrap()
+  }
+
+  fn versioned_routes() -> Routes {
+    Routes::new()
+      .route(Route::new("/plain", get(|| async { "plain" })))
+      .route(Route::new("/todo", get(|| async { "v1" })).version("1"))
+      .route(Route::new("/todo", get(|| async { "v2" })).version("2"))
+  }
+
+  #[tokio::test]
+  async fn default_version_also_mounted_without_prefix() {
+    let router =
+      Routed(versioned_routes).into_router_with(&mut (), RouterOptions::new().default_version("2"));
+
+    for (uri, body) in [
+      ("/plain", "plain"),
+      ("/v2/plain", "plain"),
+      ("/todo", "v2"),
+      ("/v2/todo", "v2"),
+      ("/v1/todo", "v1"),
+    ] {
+      assert_eq!(
+        send(&router, get_request(uri, None)).await,
+        (StatusCode::OK, body.into()),
+        "{uri}"
+      );
+    }
+
+    let (status, _) = send(&router, get_request("/v1/plain", None)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+  }
+
+  #[tokio::test]
+  async fn unknown_header_version_falls_back_to_default() {
+    let options = RouterOptions::new()
+      .versioning(Versioning::header(HeaderName::from_static("x-api-version")))
+      .default_version("2");
+    let router = Routed(versioned_routes).into_router_with(&mut (), options);
+
+    for (uri, version, body) in [
+      ("/todo", Some("1"), "v1"),
+      ("/todo", Some("2"), "v2"),
+      ("/todo", Some("3"), "v2"),
+      ("/todo", None, "v2"),
+      ("/plain", Some("3"), "plain"),
+    ] {
+      assert_eq!(
+        send(&router, get_request(uri, version)).await,
+        (StatusCode::OK, body.into()),
+        "{uri} {version:?}"
+      );
+    }
   }
 }
@@ -0,0 +1,101 @@
+use std::{
+  any::{Any, TypeId},
+  collections::HashMap,
+  sync::Arc,
+};
+
+use axum::http::request::Parts;
+
+use crate::Providers;
+
+type Factory = Arc<dyn Fn() -> Box<dyn Any + Send> + Send + Sync>;
+
+/// Replacements of injectables and providers, read from the request extensions, e.g. to swap a
+/// service for a fake in tests without changing the controllers depending on it:
+///
+/// ```ignore
+/// let overrides = Overrides::new()
+///   .injectable(TodoService::fake())
+///   .provider(Config(Arc::new(AppConfig::test())));
+///
+/// let app = TestApp::new(AppModule)
+///   .with_state(AppState::default())
+///   .with_overrides(overrides);
+/// ```
+///
+/// Outside of tests, `router.layer(Extension(overrides))` applies them to a router.
+#[derive(Clone, Default)]
+pub struct Overrides(Arc<Inner>);
+
+#[derive(Clone, Default)]
+struct Inner {
+  injectables: HashMap<TypeId, Factory>,
+  providers: Providers,
+}
+
+impl Overrides {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Replaces the injectable `T` with clones of `value`.
+  pub fn injectable<T>(self, value: T) -> Self
+  where
+    T: Clone + Send + Sync + 'static,
+  {
+    self.injectable_with(move || value.clone())
+  }
+
+  /// Replaces the injectable `T` with the values built by `factory`, once per extraction.
+  pub fn injectable_with<T, F>(mut self, factory: F) -> Self
+  where
+    T: Send + 'static,
+    F: Fn() -> T + Send + Sync + 'static,
+  {
+    let factory: Factory = Arc::new(move || Box::new(factory()));
+
+    Arc::make_mut(&mut self.0)
+      .injectables
+      .insert(TypeId::of::<T>(), factory);
+    self
+  }
+
+  /// Replaces the provider `T` registered by a module, e.g. the `Config<T>` of a `ConfigModule`.
+  pub fn provider<T>(mut self, value: T) -> Self
+  where
+    T: Send + Sync + 'static,
+  {
+    Arc::make_mut(&mut self.0).providers.insert(value);
+    self
+  }
+
+  /// The replacement of the injectable `T` for the request, called by the `FromRequestParts` impl
+  /// of `#[derive(Injectable)]`.
+  #[doc(hidden)]
+  pub fn resolve<T>(parts: &Parts) -> Option<T>
+  where
+    T: 'static,
+  {
+    let factory = parts
+      .extensions
+      .get::<Overrides>()?
+      .0
+      .injectables
+      .get(&TypeId::of::<T>())?;
+
+    factory().downcast().ok().map(|value| *value)
+  }
+
+  /// The provider `T` of the request, replaced or registered by a module. Extractors of module
+  /// providers use it to honor overrides.
+  pub fn resolve_provider<T>(parts: &Parts) -> Option<Arc<T>>
+  where
+    T: Send + Sync + 'static,
+  {
+    parts
+      .extensions
+      .get::<Overrides>()
+      .and_then(|overrides| overrides.0.providers.get())
+      .or_else(|| parts.extensions.get::<Providers>()?.get())
+  }
+}
@@ -0,0 +1,34 @@
+use axum::{
+  http::{HeaderName, HeaderValue, StatusCode},
+  response::{IntoResponse, Response},
+};
+
+/// Applies the `#[status]` and `#[header]` attributes of a handler to its response.
+///
+/// The status only replaces `200 OK`, so errors and statuses chosen by the handler are kept, and
+/// headers already set by the handler take precedence.
+pub fn shape_response<R>(
+  res: R,
+  status: Option<StatusCode>,
+  headers: &[(HeaderName, HeaderValue)],
+) -> Response
+where
+  R: IntoResponse,
+{
+  let mut res = res.into_response();
+
+  if let Some(status) = status {
+    if res.status() == StatusCode::OK {
+      *res.status_mut() = status;
+    }
+  }
+
+  for (name, value) in headers {
+    res
+      .headers_mut()
+      .entry(name)
+      .or_insert_with(|| value.clone());
+  }
+
+  res
+}
@@ -0,0 +1,132 @@
+use std::{borrow::Cow, convert::Infallible};
+
+use axum::{
+  extract::Request,
+  http::Method,
+  response::IntoResponse,
+  routing::{MethodRouter, Route as AxumRoute},
+};
+use tower::{Layer, Service};
+
+/// The routes registered by a controller, relative to the controller path.
+pub struct Routes<S = ()> {
+  pub routes: Vec<Route<S>>,
+}
+
+/// A single handler of a controller.
+pub struct Route<S = ()> {
+  pub path: Cow<'static, str>,
+  pub version: Option<Cow<'static, str>>,
+  pub method_router: MethodRouter<S>,
+  /// The methods handled by `method_router`.
+  pub methods: RouteMethods,
+  /// The controller, gateway or module registering the route, for error messages.
+  pub controller: Option<Cow<'static, str>>,
+  /// The handler function, for error messages.
+  pub handler: Option<Cow<'static, str>>,
+}
+
+impl<S> Routes<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  pub fn new() -> Self {
+    Self { routes: vec![] }
+  }
+
+  pub fn route(mut self, route: Route<S>) -> Self {
+    self.routes.push(route);
+    self
+  }
+
+  /// Sets the controller of the routes registered so far that don't have one.
+  pub fn controller(mut self, controller: impl Into<Cow<'static, str>>) -> Self {
+    let controller = controller.into();
+
+    for route in &mut self.routes {
+      route.controller.get_or_insert_with(|| controller.clone());
+    }
+
+    self
+  }
+
+  /// Applies `layer` to every route registered so far.
+  pub fn layer<L>(self, layer: L) -> Self
+  where
+    L: Layer<AxumRoute> + Clone + Send + 'static,
+    L::Service: Service<Request> + Clone + Send + 'static,
+    <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+    <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+    <L::Service as Service<Request>>::Future: Send + 'static,
+  {
+    Self {
+      routes: self
+        .routes
+        .into_iter()
+        .map(|route| Route {
+          method_router: route.method_router.layer(layer.clone()),
+          ..route
+        })
+        .collect(),
+    }
+  }
+}
+
+impl<S> Default for Routes<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// The methods handled by a [`Route`], used to find conflicts and to merge the routes of a path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RouteMethods {
+  /// Not declared, e.g. a `get(..)` router, merged with the other routes of the path by axum.
+  #[default]
+  Unknown,
+  /// Any method, e.g. an `any(..)` router.
+  Any,
+  Only(Vec<Method>),
+}
+
+impl<S> Route<S> {
+  /// A route of `method_router` at `path`. Its methods are unknown until declared with
+  /// [`Route::methods`] or [`Route::any_method`], so it isn't checked for conflicts with the other
+  /// routes of the path: `Route::new("/", get(a))` and `Route::new("/", post(b))` are merged by
+  /// axum, which panics if they both handle a method.
+  pub fn new(path: impl Into<Cow<'static, str>>, method_router: MethodRouter<S>) -> Self {
+    Self {
+      path: path.into(),
+      version: None,
+      method_router,
+      methods: RouteMethods::Unknown,
+      controller: None,
+      handler: None,
+    }
+  }
+
+  /// Declares the methods handled by the route.
+  pub fn methods(mut self, methods: &[Method]) -> Self {
+    self.methods = RouteMethods::Only(methods.to_vec());
+    self
+  }
+
+  /// Declares that the route handles any method, e.g. with `any(..)`.
+  pub fn any_method(mut self) -> Self {
+    self.methods = RouteMethods::Any;
+    self
+  }
+
+  pub fn handler(mut self, handler: impl Into<Cow<'static, str>>) -> Self {
+    self.handler = Some(handler.into());
+    self
+  }
+
+  pub fn version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+    self.version = Some(version.into());
+    self
+  }
+}
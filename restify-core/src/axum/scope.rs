@@ -0,0 +1,78 @@
+use std::{
+  any::{Any, TypeId},
+  collections::HashMap,
+  marker::PhantomData,
+  sync::Arc,
+};
+
+use axum::http::request::Parts;
+
+/// The injectables built for a request, shared by the extractors depending on them.
+#[derive(Clone, Default)]
+struct RequestScope(HashMap<TypeId, Arc<dyn Any + Send + Sync>>);
+
+/// The request-scoped cache of the injectable `T`, used by the `FromRequestParts` impl of
+/// `#[derive(Injectable)]` through [`CachedInScope`] or [`NotCachedInScope`]:
+///
+/// ```ignore
+/// (&Scoped::<T>::new()).scope_get(parts)
+/// ```
+///
+/// resolves to `CachedInScope` when `T` is `Clone + Send + Sync`, and to the no-op
+/// `NotCachedInScope` otherwise, since a value can only be shared by cloning it.
+#[doc(hidden)]
+pub struct Scoped<T>(PhantomData<fn() -> T>);
+
+impl<T> Scoped<T> {
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    Self(PhantomData)
+  }
+}
+
+#[doc(hidden)]
+pub trait CachedInScope<T> {
+  fn scope_get(&self, parts: &Parts) -> Option<T>;
+
+  fn scope_set(&self, parts: &mut Parts, value: &T);
+}
+
+impl<T> CachedInScope<T> for Scoped<T>
+where
+  T: Clone + Send + Sync + 'static,
+{
+  fn scope_get(&self, parts: &Parts) -> Option<T> {
+    let value = parts
+      .extensions
+      .get::<RequestScope>()?
+      .0
+      .get(&TypeId::of::<T>())?;
+
+    value.downcast_ref::<T>().cloned()
+  }
+
+  fn scope_set(&self, parts: &mut Parts, value: &T) {
+    if parts.extensions.get::<RequestScope>().is_none() {
+      parts.extensions.insert(RequestScope::default());
+    }
+
+    if let Some(scope) = parts.extensions.get_mut::<RequestScope>() {
+      scope.0.insert(TypeId::of::<T>(), Arc::new(value.clone()));
+    }
+  }
+}
+
+#[doc(hidden)]
+pub trait NotCachedInScope<T> {
+  fn scope_get(&self, parts: &Parts) -> Option<T>;
+
+  fn scope_set(&self, parts: &mut Parts, value: &T);
+}
+
+impl<T> NotCachedInScope<T> for &Scoped<T> {
+  fn scope_get(&self, _parts: &Parts) -> Option<T> {
+    None
+  }
+
+  fn scope_set(&self, _parts: &mut Parts, _value: &T) {}
+}
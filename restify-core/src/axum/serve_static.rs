@@ -0,0 +1,483 @@
+use std::{borrow::Cow, marker::PhantomData, path::PathBuf};
+
+use axum::{
+  extract::{MatchedPath, Request},
+  http::{header::CACHE_CONTROL, HeaderValue, Method, Uri},
+  routing::{get_service, MethodRouter},
+};
+use tower::util::MapRequestLayer;
+use tower_http::{
+  services::{ServeDir, ServeFile},
+  set_header::{SetResponseHeader, SetResponseHeaderLayer},
+};
+
+use super::{Route, Routes};
+use crate::{ControllerDetails, Module, ModuleDetails, Providers};
+
+const WILDCARD: &str = "/*path";
+
+/// A module serving static files at a prefix, from a directory or from assets embedded with
+/// [`rust_embed`] (with the `embed` feature):
+///
+/// ```ignore
+/// #[derive(Module)]
+/// #[module(imports(ServeStaticModule::dir("/", "dist").spa().precompressed()))]
+/// pub struct AppModule;
+/// ```
+///
+/// The prefix can't contain path parameters.
+pub struct ServeStaticModule<S = (), Ctx = ()> {
+  prefix: Cow<'static, str>,
+  source: Source,
+  spa: bool,
+  precompressed: bool,
+  cache_control: Option<HeaderValue>,
+  _marker: PhantomData<fn() -> (S, Ctx)>,
+}
+
+#[derive(Clone)]
+enum Source {
+  Dir(PathBuf),
+  #[cfg(feature = "embed")]
+  Embedded(embedded::GetFile),
+}
+
+impl<S, Ctx> ServeStaticModule<S, Ctx> {
+  /// Serves the files of `dir`, relative to the working directory.
+  pub fn dir(prefix: impl Into<Cow<'static, str>>, dir: impl Into<PathBuf>) -> Self {
+    Self::new(prefix.into(), Source::Dir(dir.into()))
+  }
+
+  /// Serves the assets embedded in `E`.
+  #[cfg(feature = "embed")]
+  pub fn embedded<E>(prefix: impl Into<Cow<'static, str>>) -> Self
+  where
+    E: rust_embed::RustEmbed,
+  {
+    Self::new(prefix.into(), Source::Embedded(E::get))
+  }
+
+  fn new(prefix: Cow<'static, str>, source: Source) -> Self {
+    Self {
+      prefix,
+      source,
+      spa: false,
+      precompressed: false,
+      cache_control: None,
+      _marker: PhantomData,
+    }
+  }
+
+  /// Answers the requests of missing files with `index.html`, for client-side routing.
+  ///
+  /// `index.html` is sent with `Cache-Control: no-cache`, so new deployments are picked up.
+  pub fn spa(mut self) -> Self {
+    self.spa = true;
+    self
+  }
+
+  /// Sends the `.br` and `.gz` variants of the files, if any, to the clients accepting them.
+  pub fn precompressed(mut self) -> Self {
+    self.precompressed = true;
+    self
+  }
+
+  /// The `Cache-Control` header of the files, e.g. `public, max-age=31536000, immutable` for
+  /// fingerprinted assets.
+  pub fn cache_control(mut self, value: HeaderValue) -> Self {
+    self.cache_control = Some(value);
+    self
+  }
+}
+
+impl<S, Ctx> Module for ServeStaticModule<S, Ctx>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  type Context = Ctx;
+  type ControllerContext = ();
+  type ControllerReturn = Routes<S>;
+
+  fn details(&self, _ctx: &mut Ctx) -> ModuleDetails<Ctx, (), Routes<S>> {
+    let prefix = self.prefix.clone();
+    let options = Options {
+      source: self.source.clone(),
+      spa: self.spa,
+      precompressed: self.precompressed,
+      cache_control: self.cache_control.clone(),
+    };
+
+    ModuleDetails {
+      imports: vec![],
+      controllers: vec![Box::new(move |_| {
+        let method_router = options.method_router();
+
+        ControllerDetails {
+          path: prefix.clone(),
+          return_: Routes::new()
+            .route(Route::new("/", method_router.clone()).methods(&[Method::GET]))
+            .route(Route::new(WILDCARD, method_router).methods(&[Method::GET]))
+            .controller("ServeStaticModule"),
+        }
+      })],
+      gateways: vec![],
+      providers: Providers::new(),
+    }
+  }
+}
+
+#[derive(Clone)]
+struct Options {
+  source: Source,
+  spa: bool,
+  precompressed: bool,
+  cache_control: Option<HeaderValue>,
+}
+
+impl Options {
+  fn method_router<S>(&self) -> MethodRouter<S>
+  where
+    S: Clone + Send + Sync + 'static,
+  {
+    let method_router = match &self.source {
+      Source::Dir(dir) => self.serve_dir(dir),
+      #[cfg(feature = "embed")]
+      Source::Embedded(get_file) => embedded::method_router(*get_file, self.clone()),
+    };
+
+    let method_router = match &self.cache_control {
+      Some(value) => method_router.layer(SetResponseHeaderLayer::if_not_present(
+        CACHE_CONTROL,
+        value.clone(),
+      )),
+      None => method_router,
+    };
+
+    method_router.layer(MapRequestLayer::new(strip_prefix))
+  }
+
+  fn serve_dir<S>(&self, dir: &std::path::Path) -> MethodRouter<S>
+  where
+    S: Clone + Send + Sync + 'static,
+  {
+    let mut serve_dir = ServeDir::new(dir);
+    let mut index = ServeFile::new(dir.join("index.html"));
+
+    if self.precompressed {
+      serve_dir = serve_dir.precompressed_br().precompressed_gzip();
+      index = index.precompressed_br().precompressed_gzip();
+    }
+
+    if !self.spa {
+      return get_service(serve_dir);
+    }
+
+    let index = SetResponseHeader::overriding(index, CACHE_CONTROL, no_cache());
+
+    get_service(serve_dir.fallback(index))
+  }
+}
+
+fn no_cache() -> HeaderValue {
+  HeaderValue::from_static("no-cache")
+}
+
+/// Strips the path the files are mounted at (including a version prefix) from the request URI,
+/// the same way `Router::nest_service` does.
+fn strip_prefix(mut req: Request) -> Request {
+  let Some(matched) = req.extensions().get::<MatchedPath>() else {
+    return req;
+  };
+
+  let mount = matched
+    .as_str()
+    .trim_end_matches(WILDCARD)
+    .trim_end_matches('/');
+
+  let Some(rest) = req.uri().path().strip_prefix(mount) else {
+    return req;
+  };
+
+  let path = match req.uri().query() {
+    Some(query) => format!("/{}?{query}", rest.trim_start_matches('/')),
+    None => format!("/{}", rest.trim_start_matches('/')),
+  };
+
+  let mut parts = req.uri().clone().into_parts();
+
+  if let Ok(path) = path.parse() {
+    parts.path_and_query = Some(path);
+
+    if let Ok(uri) = Uri::from_parts(parts) {
+      *req.uri_mut() = uri;
+    }
+  }
+
+  req
+}
+
+#[cfg(feature = "embed")]
+mod embedded {
+  use std::fmt::Write;
+
+  use axum::{
+    body::Body,
+    extract::Request,
+    http::{
+      header::{
+        ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY,
+      },
+      HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::{get, MethodRouter},
+  };
+  use percent_encoding::percent_decode_str;
+  use rust_embed::EmbeddedFile;
+
+  use super::{no_cache, Options};
+
+  pub(super) type GetFile = fn(&str) -> Option<EmbeddedFile>;
+
+  pub(super) fn method_router<S>(get_file: GetFile, options: Options) -> MethodRouter<S>
+  where
+    S: Clone + Send + Sync + 'static,
+  {
+    get(move |req: Request| {
+      let res = serve(get_file, &options, &req);
+      async move { res }
+    })
+  }
+
+  fn serve(get_file: GetFile, options: &Options, req: &Request) -> Response {
+    let Ok(path) = percent_decode_str(req.uri().path().trim_start_matches('/')).decode_utf8()
+    else {
+      return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let path = if path.is_empty() || path.ends_with('/') {
+      format!("{path}index.html")
+    } else {
+      path.into_owned()
+    };
+
+    if let Some(res) = file(get_file, options, &path, req.headers()) {
+      return res;
+    }
+
+    if options.spa {
+      if let Some(mut res) = file(get_file, options, "index.html", req.headers()) {
+        res.headers_mut().insert(CACHE_CONTROL, no_cache());
+        return res;
+      }
+    }
+
+    StatusCode::NOT_FOUND.into_response()
+  }
+
+  fn file(
+    get_file: GetFile,
+    options: &Options,
+    path: &str,
+    headers: &HeaderMap,
+  ) -> Option<Response> {
+    let original = get_file(path)?;
+
+    let accepts = |encoding: &str| {
+      headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|value| {
+          let mut params = value.split(';').map(str::trim);
+
+          // `q=0` marks an encoding as not acceptable
+          params.next() == Some(encoding)
+            && !params.any(|param| {
+              param
+                .strip_prefix("q=")
+                .and_then(|q| q.parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+            })
+        })
+    };
+
+    let (file, encoding) = [("br", ".br"), ("gzip", ".gz")]
+      .into_iter()
+      .filter(|_| options.precompressed)
+      .filter(|(encoding, _)| accepts(encoding))
+      .find_map(|(encoding, ext)| Some((get_file(&format!("{path}{ext}"))?, Some(encoding))))
+      .unwrap_or((original, None));
+
+    let etag = file
+      .metadata
+      .sha256_hash()
+      .iter()
+      .fold(String::from("\""), |mut etag, byte| {
+        let _ = write!(etag, "{byte:02x}");
+        etag
+      })
+      + "\"";
+
+    let mut res = if headers
+      .get(IF_NONE_MATCH)
+      .is_some_and(|value| value.as_bytes() == etag.as_bytes())
+    {
+      StatusCode::NOT_MODIFIED.into_response()
+    } else {
+      Body::from(file.data).into_response()
+    };
+
+    let res_headers = res.headers_mut();
+
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    res_headers.insert(CONTENT_TYPE, HeaderValue::from_str(mime.as_ref()).ok()?);
+    res_headers.insert(ETAG, HeaderValue::from_str(&etag).ok()?);
+
+    if let Some(encoding) = encoding {
+      res_headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+    }
+
+    if options.precompressed {
+      res_headers.insert(VARY, HeaderValue::from_static("accept-encoding"));
+    }
+
+    Some(res)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use axum::http::{HeaderValue, StatusCode};
+
+  use super::ServeStaticModule;
+  use crate::testing::TestApp;
+
+  const DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/static");
+
+  #[derive(rust_embed::RustEmbed)]
+  #[folder = "fixtures/static"]
+  struct Assets;
+
+  fn apps(module: impl Fn(ServeStaticModule) -> ServeStaticModule) -> [TestApp; 2] {
+    [
+      TestApp::new(module(ServeStaticModule::dir("/static", DIR))),
+      TestApp::new(module(ServeStaticModule::embedded::<Assets>("/static"))),
+    ]
+  }
+
+  #[tokio::test]
+  async fn serves_files() {
+    for app in apps(|module| module) {
+      app
+        .get("/static/assets/app.js")
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("console.log(\"app\");\n");
+
+      app
+        .get("/static")
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_text("<h1>App</h1>\n");
+
+      app
+        .get("/static/todo/1")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    }
+  }
+
+  #[tokio::test]
+  async fn spa_falls_back_to_index() {
+    for app in apps(ServeStaticModule::spa) {
+      app
+        .get("/static/todo/1")
+        .await
+        .assert_status(StatusCode::OK)
+        .assert_header("cache-control", "no-cache")
+        .assert_text("<h1>App</h1>\n");
+
+      let res = app.get("/static/assets/app.js").await;
+      res.assert_status(StatusCode::OK);
+      assert_eq!(res.header("cache-control"), None);
+    }
+  }
+
+  #[tokio::test]
+  async fn precompressed_files_negotiated() {
+    for app in apps(ServeStaticModule::precompressed) {
+      app
+        .get("/static/assets/app.js")
+        .header("accept-encoding", "gzip, br")
+        .await
+        .assert_header("content-encoding", "br")
+        .assert_header("content-type", "text/javascript")
+        .assert_text("brotli of app.js\n");
+
+      app
+        .get("/static/assets/app.js")
+        .header("accept-encoding", "gzip")
+        .await
+        .assert_header("content-encoding", "gzip")
+        .assert_text("gzip of app.js\n");
+
+      let res = app.get("/static/assets/app.js").await;
+      assert_eq!(res.header("content-encoding"), None);
+      res.assert_text("console.log(\"app\");\n");
+
+      let res = app
+        .get("/static/assets/app.js")
+        .header("accept-encoding", "br;q=0, gzip;q=0.5")
+        .await;
+      res.assert_header("content-encoding", "gzip");
+
+      let res = app
+        .get("/static/assets/app.js")
+        .header("accept-encoding", "br;q=0, gzip;q=0.0")
+        .await;
+      assert_eq!(res.header("content-encoding"), None);
+    }
+  }
+
+  #[tokio::test]
+  async fn cache_control_set() {
+    let immutable = || HeaderValue::from_static("public, max-age=31536000, immutable");
+
+    for app in apps(|module| module.spa().cache_control(immutable())) {
+      app
+        .get("/static/assets/app.js")
+        .await
+        .assert_header("cache-control", "public, max-age=31536000, immutable");
+
+      app
+        .get("/static/todo/1")
+        .await
+        .assert_header("cache-control", "no-cache");
+    }
+  }
+
+  #[tokio::test]
+  async fn embedded_files_have_etags() {
+    let module: ServeStaticModule = ServeStaticModule::embedded::<Assets>("/static");
+    let app = TestApp::new(module);
+
+    let res = app.get("/static/assets/app.js").await;
+    let etag = res.header("etag").unwrap().to_owned();
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+    let res = app
+      .get("/static/assets/app.js")
+      .header("if-none-match", etag.as_str())
+      .await;
+    res.assert_status(StatusCode::NOT_MODIFIED);
+    assert!(res.bytes().is_empty());
+
+    app
+      .get("/static/assets/app.js")
+      .header("if-none-match", "\"stale\"")
+      .await
+      .assert_status(StatusCode::OK);
+  }
+}
@@ -0,0 +1,124 @@
+use std::{
+  marker::PhantomData,
+  ops::Deref,
+  sync::{Arc, OnceLock},
+};
+
+use axum::{
+  async_trait,
+  extract::{FromRef, FromRequestParts},
+  http::{request::Parts, StatusCode},
+  response::{IntoResponse, Response},
+};
+
+use crate::Providers;
+
+/// A controller that is built once and shared by all its `&self` handlers, from the state
+/// through [`FromRef`] or, with `From` set to [`ViaProviders`], from the providers of the modules
+/// through [`FromProviders`].
+///
+/// Created by `#[controller("<path>", singleton)]` or `#[controller("<path>", singleton(providers))]`.
+pub struct Singleton<T, From = ViaState>(Arc<T>, PhantomData<fn() -> From>);
+
+/// A [`Singleton`] built from the state through [`FromRef`].
+pub struct ViaState;
+
+/// A [`Singleton`] built from the providers of the modules through [`FromProviders`].
+pub struct ViaProviders;
+
+/// Builds a singleton controller from the values registered by the modules, e.g. the
+/// configuration of a `ConfigModule`.
+pub trait FromProviders {
+  fn from_providers(providers: &Providers) -> Self;
+}
+
+impl<T, From> Deref for Singleton<T, From> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+/// Holds the instance of a singleton controller, inserted into the request extensions by the
+/// controller routes.
+pub struct SingletonCell<T>(Arc<OnceLock<Arc<T>>>);
+
+impl<T> SingletonCell<T> {
+  pub fn new() -> Self {
+    Self(Arc::new(OnceLock::new()))
+  }
+}
+
+impl<T> Default for SingletonCell<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> Clone for SingletonCell<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+/// The cell of `T` in the request extensions.
+fn cell<T>(parts: &Parts) -> Result<&Arc<OnceLock<Arc<T>>>, Box<Response>>
+where
+  T: Send + Sync + 'static,
+{
+  match parts.extensions.get::<SingletonCell<T>>() {
+    Some(SingletonCell(cell)) => Ok(cell),
+    None => {
+      let msg = format!(
+        "Missing singleton `{}`, is the controller mounted with `IntoRouter`?",
+        std::any::type_name::<T>()
+      );
+      Err(Box::new(
+        (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response(),
+      ))
+    }
+  }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Singleton<T, ViaState>
+where
+  T: FromRef<S> + Send + Sync + 'static,
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+    let cell = cell::<T>(parts).map_err(|res| *res)?;
+
+    Ok(Self(
+      cell.get_or_init(|| Arc::new(T::from_ref(state))).clone(),
+      PhantomData,
+    ))
+  }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Singleton<T, ViaProviders>
+where
+  T: FromProviders + Send + Sync + 'static,
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    let cell = cell::<T>(parts).map_err(|res| *res)?;
+    let build = || {
+      // The router only has the `Providers` extension when a module registered values.
+      let providers = parts
+        .extensions
+        .get::<Providers>()
+        .cloned()
+        .unwrap_or_default();
+      Arc::new(T::from_providers(&providers))
+    };
+
+    Ok(Self(cell.get_or_init(build).clone(), PhantomData))
+  }
+}
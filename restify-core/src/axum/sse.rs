@@ -0,0 +1,140 @@
+use std::{borrow::Cow, convert::Infallible, time::Duration};
+
+use axum::{
+  async_trait,
+  extract::FromRequestParts,
+  http::request::Parts,
+  response::{
+    sse::{Event, KeepAlive, Sse},
+    IntoResponse, Response,
+  },
+  BoxError,
+};
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+
+/// A typed event of a `#[sse]` stream, `data` is sent as JSON.
+pub struct SseEvent<T> {
+  pub data: T,
+  pub event: Option<Cow<'static, str>>,
+  pub id: Option<String>,
+  pub retry: Option<Duration>,
+}
+
+impl<T> SseEvent<T> {
+  pub fn new(data: T) -> Self {
+    Self {
+      data,
+      event: None,
+      id: None,
+      retry: None,
+    }
+  }
+
+  /// The event type, dispatched to `addEventListener(<event>)` listeners by browsers.
+  pub fn event(mut self, event: impl Into<Cow<'static, str>>) -> Self {
+    self.event = Some(event.into());
+    self
+  }
+
+  /// The event ID, sent back in the `Last-Event-ID` header when the client reconnects.
+  pub fn id(mut self, id: impl ToString) -> Self {
+    self.id = Some(id.to_string());
+    self
+  }
+
+  /// The reconnection time of the client.
+  pub fn retry(mut self, retry: Duration) -> Self {
+    self.retry = Some(retry);
+    self
+  }
+}
+
+/// The items of a `#[sse]` stream.
+pub trait IntoSseEvent {
+  fn into_sse_event(self) -> Result<Event, BoxError>;
+}
+
+impl IntoSseEvent for Event {
+  fn into_sse_event(self) -> Result<Event, BoxError> {
+    Ok(self)
+  }
+}
+
+impl<T> IntoSseEvent for SseEvent<T>
+where
+  T: Serialize,
+{
+  fn into_sse_event(self) -> Result<Event, BoxError> {
+    let mut event = Event::default().json_data(self.data)?;
+
+    // `Event` panics on line breaks in these fields
+    let is_valid = |field: &str| !field.contains(['\r', '\n', '\0']);
+
+    if let Some(name) = self.event {
+      if !is_valid(&name) {
+        return Err(format!("Invalid SSE event type: {name:?}").into());
+      }
+      event = event.event(name);
+    }
+
+    if let Some(id) = self.id {
+      if !is_valid(&id) {
+        return Err(format!("Invalid SSE event id: {id:?}").into());
+      }
+      event = event.id(id);
+    }
+
+    if let Some(retry) = self.retry {
+      event = event.retry(retry);
+    }
+
+    Ok(event)
+  }
+}
+
+impl<I, E> IntoSseEvent for Result<I, E>
+where
+  I: IntoSseEvent,
+  E: Into<BoxError>,
+{
+  fn into_sse_event(self) -> Result<Event, BoxError> {
+    self.map_err(Into::into)?.into_sse_event()
+  }
+}
+
+/// Turns the stream returned by a `#[sse]` handler into a `text/event-stream` response with
+/// keep-alive comments.
+///
+/// The stream is dropped as soon as the client disconnects.
+pub fn into_sse<S>(stream: S) -> Response
+where
+  S: Stream + Send + 'static,
+  S::Item: IntoSseEvent,
+{
+  Sse::new(stream.map(IntoSseEvent::into_sse_event))
+    .keep_alive(KeepAlive::default())
+    .into_response()
+}
+
+/// The `Last-Event-ID` header sent by a reconnecting client, to resume a `#[sse]` stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastEventId(pub Option<String>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for LastEventId
+where
+  S: Send + Sync,
+{
+  type Rejection = Infallible;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    let id = parts
+      .headers
+      .get("last-event-id")
+      .and_then(|id| id.to_str().ok())
+      .map(ToOwned::to_owned);
+
+    Ok(Self(id))
+  }
+}
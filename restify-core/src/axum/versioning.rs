@@ -0,0 +1,156 @@
+use std::{borrow::Cow, sync::Arc};
+
+use axum::{
+  extract::{Request, State},
+  http::{
+    header::{HeaderName, ACCEPT},
+    HeaderMap, StatusCode,
+  },
+  response::IntoResponse,
+  routing::{any, MethodRouter},
+};
+use tower::ServiceExt;
+
+/// How the requested API version is read from a request.
+#[derive(Clone, Debug)]
+pub enum Versioning {
+  /// The version is a path prefix, e.g. `/v2/todo`.
+  Uri { prefix: Cow<'static, str> },
+  /// The version is the value of a custom header, e.g. `X-Api-Version: 2`.
+  Header { name: HeaderName },
+  /// The version is a parameter of the `Accept` media type, e.g. `application/json; v=2`.
+  MediaType { key: Cow<'static, str> },
+}
+
+impl Versioning {
+  pub fn uri() -> Self {
+    Self::Uri { prefix: "v".into() }
+  }
+
+  pub fn header(name: HeaderName) -> Self {
+    Self::Header { name }
+  }
+
+  pub fn media_type(key: impl Into<Cow<'static, str>>) -> Self {
+    Self::MediaType { key: key.into() }
+  }
+
+  fn extract<'a>(&self, headers: &'a HeaderMap) -> Option<&'a str> {
+    match self {
+      Versioning::Uri { .. } => None,
+      Versioning::Header { name } => headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim),
+      Versioning::MediaType { key } => headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .flat_map(|media_type| media_type.split(';').skip(1))
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim().trim_matches('"')),
+    }
+  }
+}
+
+impl Default for Versioning {
+  fn default() -> Self {
+    Self::uri()
+  }
+}
+
+/// Options used by [`IntoRouter`](super::IntoRouter) when building the router.
+#[derive(Clone, Debug, Default)]
+pub struct RouterOptions {
+  pub versioning: Versioning,
+  /// The version of routes that don't declare one, and of requests that don't ask for one.
+  pub default_version: Option<Cow<'static, str>>,
+}
+
+impl RouterOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn versioning(mut self, versioning: Versioning) -> Self {
+    self.versioning = versioning;
+    self
+  }
+
+  pub fn default_version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+    self.default_version = Some(version.into());
+    self
+  }
+}
+
+pub(crate) type VersionedRoutes<S> = Vec<(Option<Cow<'static, str>>, MethodRouter<S>)>;
+
+/// Builds a method router that forwards each request to the handlers of the requested version.
+/// Requests without a version, or asking for a version without handlers at this path, fall back to
+/// the default version and then to the version-neutral handlers.
+pub(crate) fn dispatch<S>(
+  versioning: Versioning,
+  default_version: Option<Cow<'static, str>>,
+  routes: VersionedRoutes<S>,
+) -> MethodRouter<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  let routes = Arc::new(routes);
+
+  any(move |State(state): State<S>, req: Request| {
+    let routes = routes.clone();
+    let version = versioning.extract(req.headers()).map(str::to_owned);
+    let default_version = default_version.clone();
+
+    async move {
+      let find = |version: Option<&str>| {
+        routes
+          .iter()
+          .find(|(v, _)| version.is_some() && v.as_deref() == version)
+      };
+
+      let method_router = find(version.as_deref())
+        .or_else(|| find(default_version.as_deref()))
+        .or_else(|| routes.iter().find(|(v, _)| v.is_none()))
+        .map(|(_, method_router)| method_router.clone());
+
+      match method_router {
+        Some(method_router) => match method_router.with_state(state).oneshot(req).await {
+          Ok(res) => res,
+          Err(err) => match err {},
+        },
+        None => StatusCode::NOT_FOUND.into_response(),
+      }
+    }
+  })
+}
+
+pub(crate) fn uri_prefix(prefix: &str, version: &str) -> String {
+  format!("/{prefix}{version}")
+}
+
+#[cfg(test)]
+mod test {
+  use axum::http::{header::ACCEPT, HeaderMap, HeaderValue};
+
+  use super::Versioning;
+
+  #[test]
+  fn version_extracted_from_request_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api-version", HeaderValue::from_static(" 2 "));
+    headers.insert(
+      ACCEPT,
+      HeaderValue::from_static("text/html, application/json; charset=utf-8; v=\"3\""),
+    );
+
+    let header = Versioning::header("x-api-version".parse().unwrap());
+    assert_eq!(header.extract(&headers), Some("2"));
+    assert_eq!(Versioning::media_type("v").extract(&headers), Some("3"));
+    assert_eq!(Versioning::media_type("version").extract(&headers), None);
+    assert_eq!(Versioning::uri().extract(&headers), None);
+  }
+}
@@ -0,0 +1,179 @@
+use std::{error::Error, sync::Arc};
+
+use axum::{
+  async_trait,
+  extract::FromRequestParts,
+  http::{request::Parts, StatusCode},
+  response::{Html, IntoResponse, Response},
+  Extension,
+};
+use serde::Serialize;
+
+/// A template rendered by `#[render("<name>")]`, `dir` is the `[views] dir` of `restify.toml`.
+#[derive(Debug, Clone, Copy)]
+pub struct Template {
+  pub name: &'static str,
+  pub dir: &'static str,
+}
+
+pub type RenderError = Box<dyn Error + Send + Sync>;
+
+/// The error of a template that failed to render, in the extensions of the `500` response.
+#[derive(Debug, Clone)]
+pub struct RenderFailure {
+  pub template: Template,
+  pub error: Arc<RenderError>,
+}
+
+/// Renders the templates of `#[render]` handlers, e.g. with minijinja or askama.
+pub trait TemplateEngine: Send + Sync + 'static {
+  fn render(&self, template: &Template, context: serde_json::Value) -> Result<String, RenderError>;
+}
+
+/// The template engine of the application, added to the router as an extension:
+///
+/// ```ignore
+/// router.layer(Extension(Views::new(MiniJinja::new())))
+/// ```
+#[derive(Clone)]
+pub struct Views(Arc<dyn TemplateEngine>);
+
+impl Views {
+  pub fn new(engine: impl TemplateEngine) -> Self {
+    Self(Arc::new(engine))
+  }
+
+  /// Renders `template` with `context` into HTML.
+  pub fn try_render<T>(&self, template: &Template, context: T) -> Result<String, RenderError>
+  where
+    T: Serialize,
+  {
+    let context = serde_json::to_value(context)?;
+    self.0.render(template, context)
+  }
+
+  /// Renders `template` with `context` into an HTML response, or `500 Internal Server Error` if
+  /// rendering fails, with the error in a [`RenderFailure`] extension of the response for the
+  /// layers logging it.
+  pub fn render<T>(&self, template: Template, context: T) -> Response
+  where
+    T: Serialize,
+  {
+    match self.try_render(&template, context) {
+      Ok(html) => Html(html).into_response(),
+      Err(error) => {
+        let msg = format!("Failed to render template `{}`", template.name);
+        let failure = RenderFailure {
+          template,
+          error: Arc::new(error),
+        };
+        (StatusCode::INTERNAL_SERVER_ERROR, Extension(failure), msg).into_response()
+      }
+    }
+  }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Views
+where
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    parts.extensions.get::<Views>().cloned().ok_or_else(|| {
+      let msg = "Missing views, add `Extension(Views::new(..))` to the router";
+      (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+    })
+  }
+}
+
+#[cfg(feature = "minijinja")]
+pub use self::minijinja::MiniJinja;
+
+#[cfg(feature = "minijinja")]
+mod minijinja {
+  use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+  };
+
+  use minijinja::{path_loader, Environment};
+
+  use super::{RenderError, Template, TemplateEngine};
+
+  type Setup = Box<dyn Fn(&mut Environment<'static>) + Send + Sync>;
+
+  /// A [`TemplateEngine`] loading the templates from their directory with minijinja.
+  #[derive(Default)]
+  pub struct MiniJinja {
+    envs: RwLock<HashMap<&'static str, Arc<Environment<'static>>>>,
+    root: Option<PathBuf>,
+    setup: Option<Setup>,
+  }
+
+  impl MiniJinja {
+    pub fn new() -> Self {
+      Self::default()
+    }
+
+    /// The directory the `[views] dir` is relative to, the working directory by default, e.g. the
+    /// directory of `restify.toml` when the binary is run from elsewhere.
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+      self.root = Some(root.into());
+      self
+    }
+
+    /// Configures the environments, e.g. to add filters or globals.
+    pub fn setup(
+      mut self,
+      setup: impl Fn(&mut Environment<'static>) + Send + Sync + 'static,
+    ) -> Self {
+      self.setup = Some(Box::new(setup));
+      self
+    }
+
+    fn env(&self, dir: &'static str) -> Arc<Environment<'static>> {
+      if let Some(env) = self.envs.read().unwrap().get(dir) {
+        return env.clone();
+      }
+
+      self
+        .envs
+        .write()
+        .unwrap()
+        .entry(dir)
+        .or_insert_with(|| {
+          let mut env = Environment::new();
+
+          match &self.root {
+            Some(root) => env.set_loader(path_loader(root.join(dir))),
+            None => env.set_loader(path_loader(dir)),
+          }
+
+          if let Some(setup) = &self.setup {
+            setup(&mut env);
+          }
+
+          Arc::new(env)
+        })
+        .clone()
+    }
+  }
+
+  impl TemplateEngine for MiniJinja {
+    fn render(
+      &self,
+      template: &Template,
+      context: serde_json::Value,
+    ) -> Result<String, RenderError> {
+      Ok(
+        self
+          .env(template.dir)
+          .get_template(template.name)?
+          .render(context)?,
+      )
+    }
+  }
+}
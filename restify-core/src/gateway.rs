@@ -0,0 +1,9 @@
+use crate::ControllerDetails;
+
+/// A WebSocket endpoint of a module, mounted at its path like a controller.
+pub trait Gateway {
+  type Context;
+  type Return;
+
+  fn configure(ctx: &mut Self::Context) -> ControllerDetails<Self::Return>;
+}
@@ -1,9 +1,16 @@
 mod controller;
+mod gateway;
 mod module;
+pub mod path;
+mod providers;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use controller::Controller;
 pub use controller::ControllerDetails;
+pub use gateway::Gateway;
 pub use module::{Module, ModuleDetails};
+pub use providers::{ProviderError, Providers};
 
 #[cfg(feature = "axum")]
 pub mod axum;
@@ -1,4 +1,4 @@
-use crate::{BoxedControllerFn, BoxedModule};
+use crate::{BoxedControllerFn, BoxedModule, Providers};
 
 pub trait Module {
   type Context;
@@ -14,17 +14,20 @@ pub trait Module {
 pub struct ModuleDetails<Ctx, ConCtx, ConRet> {
   pub imports: Vec<BoxedModule<Ctx, ConCtx, ConRet>>,
   pub controllers: Vec<BoxedControllerFn<ConCtx, ConRet>>,
+  pub gateways: Vec<BoxedControllerFn<ConCtx, ConRet>>,
+  pub providers: Providers,
 }
 
 #[allow(dead_code)]
 pub(crate) fn resolve_module<Ctx, ConCtx, ConRet>(
   module: &dyn Module<Context = Ctx, ControllerContext = ConCtx, ControllerReturn = ConRet>,
   context: &mut Ctx,
-) -> Vec<BoxedControllerFn<ConCtx, ConRet>> {
+) -> (Vec<BoxedControllerFn<ConCtx, ConRet>>, Providers) {
   let mut controllers = vec![];
-  configure_module_recursive(module, context, &mut controllers);
+  let mut providers = Providers::new();
+  configure_module_recursive(module, context, &mut controllers, &mut providers);
 
-  controllers
+  (controllers, providers)
 }
 
 #[allow(dead_code)]
@@ -32,11 +35,14 @@ fn configure_module_recursive<Ctx, ConCtx, ConRet>(
   module: &dyn Module<Context = Ctx, ControllerContext = ConCtx, ControllerReturn = ConRet>,
   context: &mut Ctx,
   controllers: &mut Vec<BoxedControllerFn<ConCtx, ConRet>>,
+  providers: &mut Providers,
 ) {
   let details = module.details(context);
   controllers.extend(details.controllers);
+  controllers.extend(details.gateways);
+  providers.merge(details.providers);
 
   for imported_module in details.imports {
-    configure_module_recursive(&*imported_module, context, controllers);
+    configure_module_recursive(&*imported_module, context, controllers, providers);
   }
 }
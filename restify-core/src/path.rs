@@ -0,0 +1,367 @@
+use std::{error::Error, fmt};
+
+/// A route path in the syntax shared by every adapter, e.g. `/todo/{id}`, `/todo/{id:uuid}` or
+/// `/files/{*path}`, translated into the syntax of the adapter and into OpenAPI path keys.
+///
+/// Axum's `/todo/:id` and `/files/*path` are accepted too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTemplate {
+  segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+  Static(String),
+  /// `{name}` or `{name:kind}`, a whole segment.
+  Param {
+    name: String,
+    kind: ParamKind,
+  },
+  /// `{*name}`, the rest of the path.
+  Wildcard {
+    name: String,
+  },
+}
+
+/// The type of a `{name:kind}` parameter. Requests whose parameter doesn't match are answered with
+/// `404 Not Found`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParamKind {
+  #[default]
+  String,
+  /// `int`, a signed integer.
+  Int,
+  /// `uint`, an unsigned integer.
+  Uint,
+  /// `uuid`, hyphenated or not.
+  Uuid,
+}
+
+impl ParamKind {
+  fn parse(kind: &str) -> Option<Self> {
+    match kind {
+      "string" => Some(Self::String),
+      "int" => Some(Self::Int),
+      "uint" => Some(Self::Uint),
+      "uuid" => Some(Self::Uuid),
+      _ => None,
+    }
+  }
+
+  pub fn matches(&self, value: &str) -> bool {
+    let digits = |value: &str| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+
+    match self {
+      Self::String => true,
+      Self::Int => digits(value.strip_prefix('-').unwrap_or(value)),
+      Self::Uint => digits(value),
+      Self::Uuid => {
+        let hex =
+          |part: &str, len| part.len() == len && part.bytes().all(|b| b.is_ascii_hexdigit());
+        let parts: Vec<_> = value.split('-').collect();
+
+        match parts[..] {
+          [simple] => hex(simple, 32),
+          [a, b, c, d, e] => hex(a, 8) && hex(b, 4) && hex(c, 4) && hex(d, 4) && hex(e, 12),
+          _ => false,
+        }
+      }
+    }
+  }
+
+  /// The `type` and `format` of the parameter schema in OpenAPI.
+  pub fn openapi_type(&self) -> (&'static str, Option<&'static str>) {
+    match self {
+      Self::String => ("string", None),
+      Self::Int => ("integer", Some("int64")),
+      Self::Uint => ("integer", Some("uint64")),
+      Self::Uuid => ("string", Some("uuid")),
+    }
+  }
+}
+
+impl fmt::Display for ParamKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::String => "string",
+      Self::Int => "int",
+      Self::Uint => "uint",
+      Self::Uuid => "uuid",
+    })
+  }
+}
+
+/// A malformed [`PathTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+  /// The path doesn't start with `/`.
+  MissingSlash,
+  /// A `{` or `}` inside a segment, e.g. `/file{id}.json`; parameters are whole segments.
+  PartialSegment(String),
+  InvalidName(String),
+  UnknownKind(String),
+  DuplicateParam(String),
+  /// A `{*name}` that isn't the last segment.
+  WildcardNotLast(String),
+}
+
+impl fmt::Display for PathError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::MissingSlash => f.write_str("Path must start with `/`"),
+      Self::PartialSegment(segment) => write!(
+        f,
+        "Invalid segment `{segment}`, parameters must be whole segments like `{{id}}`"
+      ),
+      Self::InvalidName(name) => write!(
+        f,
+        "Invalid parameter name `{name}`, expected letters, digits and `_`"
+      ),
+      Self::UnknownKind(kind) => write!(
+        f,
+        "Unknown parameter type `{kind}`, expected `string`, `int`, `uint` or `uuid`"
+      ),
+      Self::DuplicateParam(name) => write!(f, "Parameter `{name}` is defined more than once"),
+      Self::WildcardNotLast(name) => write!(f, "Wildcard `{name}` must be the last segment"),
+    }
+  }
+}
+
+impl Error for PathError {}
+
+impl PathTemplate {
+  pub fn parse(path: &str) -> Result<Self, PathError> {
+    let Some(rest) = path.strip_prefix('/') else {
+      return Err(PathError::MissingSlash);
+    };
+
+    let mut segments: Vec<Segment> = vec![];
+
+    for segment in rest.split('/') {
+      if let Some(Segment::Wildcard { name }) = segments.last() {
+        return Err(PathError::WildcardNotLast(name.clone()));
+      }
+
+      let segment = parse_segment(segment)?;
+
+      if let Some(name) = segment.name() {
+        if segments.iter().any(|s| s.name() == Some(name)) {
+          return Err(PathError::DuplicateParam(name.to_owned()));
+        }
+      }
+
+      segments.push(segment);
+    }
+
+    Ok(Self { segments })
+  }
+
+  pub fn segments(&self) -> &[Segment] {
+    &self.segments
+  }
+
+  /// The parameters and wildcards, in order, with their types.
+  pub fn params(&self) -> impl Iterator<Item = (&str, ParamKind)> {
+    self.segments.iter().filter_map(|segment| match segment {
+      Segment::Static(_) => None,
+      Segment::Param { name, kind } => Some((name.as_str(), *kind)),
+      Segment::Wildcard { name } => Some((name.as_str(), ParamKind::String)),
+    })
+  }
+
+  /// The path in axum's syntax, e.g. `/todo/:id`.
+  pub fn to_axum(&self) -> String {
+    self.render(|segment| match segment {
+      Segment::Static(s) => s.clone(),
+      Segment::Param { name, .. } => format!(":{name}"),
+      Segment::Wildcard { name } => format!("*{name}"),
+    })
+  }
+
+  /// The key of the path in an OpenAPI document, e.g. `/todo/{id}`.
+  pub fn to_openapi(&self) -> String {
+    self.render(|segment| match segment {
+      Segment::Static(s) => s.clone(),
+      Segment::Param { name, .. } | Segment::Wildcard { name } => format!("{{{name}}}"),
+    })
+  }
+
+  fn render(&self, f: impl Fn(&Segment) -> String) -> String {
+    let segments: Vec<_> = self.segments.iter().map(f).collect();
+    format!("/{}", segments.join("/"))
+  }
+}
+
+impl fmt::Display for PathTemplate {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let path = self.render(|segment| match segment {
+      Segment::Static(s) => s.clone(),
+      Segment::Param {
+        name,
+        kind: ParamKind::String,
+      } => format!("{{{name}}}"),
+      Segment::Param { name, kind } => format!("{{{name}:{kind}}}"),
+      Segment::Wildcard { name } => format!("{{*{name}}}"),
+    });
+
+    f.write_str(&path)
+  }
+}
+
+impl Segment {
+  fn name(&self) -> Option<&str> {
+    match self {
+      Segment::Static(_) => None,
+      Segment::Param { name, .. } | Segment::Wildcard { name } => Some(name),
+    }
+  }
+}
+
+fn parse_segment(segment: &str) -> Result<Segment, PathError> {
+  let name = |name: &str| {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+      Ok(name.to_owned())
+    } else {
+      Err(PathError::InvalidName(name.to_owned()))
+    }
+  };
+
+  if let Some(inner) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+    if let Some(wildcard) = inner.strip_prefix('*') {
+      return Ok(Segment::Wildcard {
+        name: name(wildcard)?,
+      });
+    }
+
+    let (param, kind) = match inner.split_once(':') {
+      Some((param, kind)) => (
+        param,
+        ParamKind::parse(kind).ok_or_else(|| PathError::UnknownKind(kind.to_owned()))?,
+      ),
+      None => (inner, ParamKind::String),
+    };
+
+    return Ok(Segment::Param {
+      name: name(param)?,
+      kind,
+    });
+  }
+
+  if segment.contains(['{', '}']) {
+    return Err(PathError::PartialSegment(segment.to_owned()));
+  }
+
+  if let Some(param) = segment.strip_prefix(':') {
+    return Ok(Segment::Param {
+      name: name(param)?,
+      kind: ParamKind::String,
+    });
+  }
+
+  if let Some(wildcard) = segment.strip_prefix('*') {
+    return Ok(Segment::Wildcard {
+      name: name(wildcard)?,
+    });
+  }
+
+  Ok(Segment::Static(segment.to_owned()))
+}
+
+/// Joins a controller path and a route path the same way `axum::Router::nest` does.
+pub fn join_paths(prefix: &str, path: &str) -> String {
+  let prefix = prefix.trim_start_matches('/');
+  let path = path.trim_start_matches('/');
+
+  match (prefix.is_empty(), path.is_empty()) {
+    (true, _) => format!("/{path}"),
+    (false, true) => format!("/{prefix}"),
+    (false, false) => format!("/{}/{path}", prefix.trim_end_matches('/')),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{join_paths, ParamKind, PathError, PathTemplate};
+
+  #[test]
+  fn templates_translated() {
+    let path = PathTemplate::parse("/todo/{id:uuid}/files/{*path}").unwrap();
+    assert_eq!(path.to_axum(), "/todo/:id/files/*path");
+    assert_eq!(path.to_openapi(), "/todo/{id}/files/{path}");
+    assert_eq!(path.to_string(), "/todo/{id:uuid}/files/{*path}");
+
+    let legacy = PathTemplate::parse("/todo/:id/").unwrap();
+    assert_eq!(legacy.to_axum(), "/todo/:id/");
+    assert_eq!(legacy.to_string(), "/todo/{id}/");
+    assert_eq!(PathTemplate::parse("/").unwrap().to_axum(), "/");
+  }
+
+  #[test]
+  fn invalid_templates_rejected() {
+    let err = |path| PathTemplate::parse(path).unwrap_err();
+
+    assert_eq!(err("todo"), PathError::MissingSlash);
+    assert_eq!(
+      err("/file{id}.json"),
+      PathError::PartialSegment("file{id}.json".into())
+    );
+    assert_eq!(err("/{id:date}"), PathError::UnknownKind("date".into()));
+    assert_eq!(err("/{id}/{id}"), PathError::DuplicateParam("id".into()));
+    assert_eq!(err("/{*rest}/x"), PathError::WildcardNotLast("rest".into()));
+    assert_eq!(err("/{}"), PathError::InvalidName("".into()));
+  }
+
+  #[test]
+  fn kinds_matched() {
+    assert!(ParamKind::Int.matches("-12"));
+    assert!(!ParamKind::Uint.matches("-12"));
+    assert!(ParamKind::Uuid.matches("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+    assert!(!ParamKind::Uuid.matches("67e55044"));
+  }
+
+  #[test]
+  fn paths_joined_like_nested_routers() {
+    assert_eq!(join_paths("/", "/"), "/");
+    assert_eq!(join_paths("/todo", "/"), "/todo");
+    assert_eq!(join_paths("/todo/", "/:id"), "/todo/:id");
+    assert_eq!(join_paths("/v2", "/todo"), "/v2/todo");
+    assert_eq!(join_paths("", "/todo"), "/todo");
+  }
+
+  /// The cases shared with the path checks of `restify-macros`, which can't depend on this crate.
+  #[test]
+  fn shared_cases() {
+    for (input, expected) in cases() {
+      let actual = match input.split_once(" + ") {
+        Some((prefix, path)) => join_paths(empty(prefix), empty(path)),
+        None => match PathTemplate::parse(input) {
+          Ok(template) => template.to_axum(),
+          Err(err) => format!("! {err}"),
+        },
+      };
+
+      match expected.strip_prefix("! ") {
+        Some(error) => assert!(
+          actual.starts_with("! ") && actual.contains(error),
+          "`{input}`: {actual}"
+        ),
+        None => assert_eq!(actual, expected, "`{input}`"),
+      }
+    }
+  }
+
+  fn cases() -> impl Iterator<Item = (&'static str, &'static str)> {
+    include_str!("path_cases.txt")
+      .lines()
+      .filter(|line| !line.is_empty() && !line.starts_with('#'))
+      .map(|line| line.split_once(" => ").expect("`<input> => <expected>`"))
+  }
+
+  fn empty(value: &str) -> &str {
+    if value == "\"\"" {
+      ""
+    } else {
+      value
+    }
+  }
+}
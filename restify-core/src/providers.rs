@@ -0,0 +1,123 @@
+use std::{
+  any::{type_name, Any, TypeId},
+  collections::HashMap,
+  error::Error,
+  fmt,
+  sync::Arc,
+};
+
+/// Values registered by modules (e.g. the loaded configuration of `ConfigModule`), shared with the
+/// handlers of the whole application.
+#[derive(Default, Clone)]
+pub struct Providers {
+  values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+  lists: HashMap<TypeId, Vec<Arc<dyn Any + Send + Sync>>>,
+  errors: Vec<ProviderError>,
+}
+
+impl Providers {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Registers `value`, replacing the previous value of the same type.
+  pub fn insert<T>(&mut self, value: T)
+  where
+    T: Send + Sync + 'static,
+  {
+    self.values.insert(TypeId::of::<T>(), Arc::new(value));
+  }
+
+  pub fn get<T>(&self) -> Option<Arc<T>>
+  where
+    T: Send + Sync + 'static,
+  {
+    self.values.get(&TypeId::of::<T>())?.clone().downcast().ok()
+  }
+
+  /// Adds `value` to the values of its type registered by every module, e.g. health indicators.
+  pub fn add<T>(&mut self, value: T)
+  where
+    T: Send + Sync + 'static,
+  {
+    self
+      .lists
+      .entry(TypeId::of::<T>())
+      .or_default()
+      .push(Arc::new(value));
+  }
+
+  /// The values of type `T` added with [`add`](Self::add), in the order of the modules.
+  pub fn all<T>(&self) -> impl Iterator<Item = Arc<T>> + '_
+  where
+    T: Send + Sync + 'static,
+  {
+    self
+      .lists
+      .get(&TypeId::of::<T>())
+      .into_iter()
+      .flatten()
+      .filter_map(|value| value.clone().downcast().ok())
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty() && self.lists.is_empty()
+  }
+
+  /// Records a value that couldn't be built, e.g. a configuration that can't be loaded, building
+  /// the router then fails with it.
+  pub fn fail(&mut self, error: ProviderError) {
+    self.errors.push(error);
+  }
+
+  /// The values that couldn't be built, in the order of the modules.
+  pub fn errors(&self) -> &[ProviderError] {
+    &self.errors
+  }
+
+  /// Adds the values of `other`, keeping the values already registered.
+  pub fn merge(&mut self, other: Providers) {
+    for (id, value) in other.values {
+      self.values.entry(id).or_insert(value);
+    }
+
+    for (id, values) in other.lists {
+      self.lists.entry(id).or_default().extend(values);
+    }
+
+    self.errors.extend(other.errors);
+  }
+}
+
+/// A value a module failed to build, returned when building the router.
+#[derive(Debug, Clone)]
+pub struct ProviderError {
+  provider: &'static str,
+  error: Arc<dyn Error + Send + Sync>,
+}
+
+impl ProviderError {
+  pub fn new<T>(error: impl Error + Send + Sync + 'static) -> Self {
+    Self {
+      provider: type_name::<T>(),
+      error: Arc::new(error),
+    }
+  }
+
+  /// The type name of the value.
+  pub fn provider(&self) -> &'static str {
+    self.provider
+  }
+}
+
+impl fmt::Display for ProviderError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Failed to load `{}`: {}", self.provider, self.error)
+  }
+}
+
+impl Error for ProviderError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(&*self.error)
+  }
+}
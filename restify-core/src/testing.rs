@@ -0,0 +1,489 @@
+//! Sends requests to the router of a module tree in-process, without binding a socket.
+//!
+//! ```ignore
+//! let app = TestApp::new(AppModule).with_state(AppState::default());
+//!
+//! app
+//!   .post("/todo")
+//!   .json(&json!({ "title": "Write tests" }))
+//!   .await
+//!   .assert_status(StatusCode::CREATED);
+//! ```
+
+use std::{
+  collections::BTreeMap,
+  fmt::{self, Debug},
+  future::{Future, IntoFuture},
+  pin::Pin,
+  sync::{Arc, Mutex},
+};
+
+use axum::{
+  body::{to_bytes, Body, Bytes},
+  extract::Request,
+  http::{
+    header::{CONTENT_TYPE, COOKIE, SET_COOKIE},
+    HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+  },
+  Extension,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tower::ServiceExt;
+
+use crate::axum::{IntoRouter, Overrides, Router, RouterError, RouterOptions};
+
+/// The router of a module tree, with the cookies set by its responses.
+///
+/// Cookies set with `Set-Cookie` are sent with the following requests, like a browser would.
+pub struct TestApp<S = ()> {
+  router: Router<S>,
+  cookies: Arc<Mutex<BTreeMap<String, String>>>,
+}
+
+impl<S> TestApp<S>
+where
+  S: Clone + Send + Sync + 'static,
+{
+  /// Builds the router of `module` with [`IntoRouter::into_router`].
+  ///
+  /// # Panics
+  ///
+  /// If a provider can't be built, a path is invalid or two routes conflict, see
+  /// [`try_new`](Self::try_new).
+  pub fn new<M, Ctx>(module: M) -> Self
+  where
+    M: IntoRouter<S, Ctx>,
+    Ctx: Default,
+  {
+    Self::new_with(module, &mut Ctx::default(), RouterOptions::default())
+  }
+
+  /// Builds the router of `module` with a module context and router options.
+  pub fn new_with<M, Ctx>(module: M, ctx: &mut Ctx, options: RouterOptions) -> Self
+  where
+    M: IntoRouter<S, Ctx>,
+  {
+    Self::from_router(module.into_router_with(ctx, options))
+  }
+
+  /// Builds the router of `module` with [`IntoRouter::try_into_router`], e.g. to test that a
+  /// module tree is rejected.
+  pub fn try_new<M, Ctx>(module: M) -> Result<Self, RouterError>
+  where
+    M: IntoRouter<S, Ctx>,
+    Ctx: Default,
+  {
+    Self::try_new_with(module, &mut Ctx::default(), RouterOptions::default())
+  }
+
+  pub fn try_new_with<M, Ctx>(
+    module: M,
+    ctx: &mut Ctx,
+    options: RouterOptions,
+  ) -> Result<Self, RouterError>
+  where
+    M: IntoRouter<S, Ctx>,
+  {
+    Ok(Self::from_router(
+      module.try_into_router_with(ctx, options)?,
+    ))
+  }
+
+  /// Tests a router built beforehand, e.g. with extra layers.
+  pub fn from_router(router: Router<S>) -> Self {
+    Self {
+      router,
+      cookies: Default::default(),
+    }
+  }
+
+  pub fn with_state(self, state: S) -> TestApp {
+    TestApp {
+      router: self.router.with_state(state),
+      cookies: self.cookies,
+    }
+  }
+}
+
+impl TestApp {
+  /// Replaces injectables and providers in every request, see [`Overrides`].
+  pub fn with_overrides(self, overrides: Overrides) -> Self {
+    Self {
+      router: self.router.layer(Extension(overrides)),
+      cookies: self.cookies,
+    }
+  }
+
+  pub fn get(&self, uri: &str) -> TestRequest<'_> {
+    self.request(Method::GET, uri)
+  }
+
+  pub fn post(&self, uri: &str) -> TestRequest<'_> {
+    self.request(Method::POST, uri)
+  }
+
+  pub fn put(&self, uri: &str) -> TestRequest<'_> {
+    self.request(Method::PUT, uri)
+  }
+
+  pub fn patch(&self, uri: &str) -> TestRequest<'_> {
+    self.request(Method::PATCH, uri)
+  }
+
+  pub fn delete(&self, uri: &str) -> TestRequest<'_> {
+    self.request(Method::DELETE, uri)
+  }
+
+  pub fn request(&self, method: Method, uri: &str) -> TestRequest<'_> {
+    let builder = Request::builder().method(method).uri(uri);
+
+    TestRequest {
+      app: self,
+      request: builder.body(Body::empty()).expect("Invalid request URI"),
+      cookies: BTreeMap::new(),
+    }
+  }
+
+  /// The value of a cookie set by a previous response.
+  pub fn cookie(&self, name: &str) -> Option<String> {
+    self.cookies.lock().unwrap().get(name).cloned()
+  }
+
+  pub fn clear_cookies(&self) {
+    self.cookies.lock().unwrap().clear();
+  }
+
+  /// Sends `request` as is, without the cookies of the app.
+  pub async fn send(&self, request: Request) -> TestResponse {
+    let response = self.router.clone().oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+
+    let body = to_bytes(body, usize::MAX)
+      .await
+      .expect("Failed to read the response body");
+
+    let mut cookies = self.cookies.lock().unwrap();
+
+    for value in parts.headers.get_all(SET_COOKIE) {
+      match value.to_str().ok().and_then(parse_set_cookie) {
+        Some((name, Some(value))) => cookies.insert(name, value),
+        Some((name, None)) => cookies.remove(&name),
+        None => None,
+      };
+    }
+
+    TestResponse {
+      status: parts.status,
+      headers: parts.headers,
+      body,
+    }
+  }
+}
+
+/// A request of a [`TestApp`], sent when awaited.
+pub struct TestRequest<'a> {
+  app: &'a TestApp,
+  request: Request,
+  cookies: BTreeMap<String, String>,
+}
+
+impl TestRequest<'_> {
+  /// # Panics
+  ///
+  /// If the name or the value is invalid.
+  pub fn header<K, V>(mut self, name: K, value: V) -> Self
+  where
+    K: TryInto<HeaderName>,
+    K::Error: Debug,
+    V: TryInto<HeaderValue>,
+    V::Error: Debug,
+  {
+    let name = name.try_into().expect("Invalid header name");
+    let value = value.try_into().expect("Invalid header value");
+
+    self.request.headers_mut().append(name, value);
+    self
+  }
+
+  /// Sends a cookie, besides the ones set by previous responses.
+  pub fn cookie(mut self, name: &str, value: &str) -> Self {
+    self.cookies.insert(name.to_owned(), value.to_owned());
+    self
+  }
+
+  /// Sets `value` as the JSON body.
+  pub fn json<T>(self, value: &T) -> Self
+  where
+    T: Serialize + ?Sized,
+  {
+    let body = serde_json::to_vec(value).expect("Failed to serialize the JSON body");
+
+    self.header(CONTENT_TYPE, "application/json").body(body)
+  }
+
+  pub fn body(mut self, body: impl Into<Body>) -> Self {
+    *self.request.body_mut() = body.into();
+    self
+  }
+
+  pub async fn send(self) -> TestResponse {
+    let TestRequest {
+      app,
+      mut request,
+      cookies,
+    } = self;
+
+    let mut all = app.cookies.lock().unwrap().clone();
+    all.extend(cookies);
+
+    if !all.is_empty() {
+      let cookie: Vec<_> = all
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+      let cookie = HeaderValue::try_from(cookie.join("; ")).expect("Invalid cookie");
+
+      request.headers_mut().insert(COOKIE, cookie);
+    }
+
+    app.send(request).await
+  }
+}
+
+impl<'a> IntoFuture for TestRequest<'a> {
+  type Output = TestResponse;
+  type IntoFuture = Pin<Box<dyn Future<Output = TestResponse> + Send + 'a>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(self.send())
+  }
+}
+
+/// A response of a [`TestApp`], with its whole body. The assertions panic with the body in the
+/// message.
+pub struct TestResponse {
+  status: StatusCode,
+  headers: HeaderMap,
+  body: Bytes,
+}
+
+impl TestResponse {
+  pub fn status(&self) -> StatusCode {
+    self.status
+  }
+
+  pub fn headers(&self) -> &HeaderMap {
+    &self.headers
+  }
+
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.get(name).and_then(|value| value.to_str().ok())
+  }
+
+  pub fn bytes(&self) -> &Bytes {
+    &self.body
+  }
+
+  pub fn text(&self) -> String {
+    String::from_utf8_lossy(&self.body).into_owned()
+  }
+
+  /// # Panics
+  ///
+  /// If the body isn't a JSON `T`.
+  pub fn json<T>(&self) -> T
+  where
+    T: DeserializeOwned,
+  {
+    serde_json::from_slice(&self.body)
+      .unwrap_or_else(|err| panic!("Invalid JSON body: {err}\n{self:?}"))
+  }
+
+  #[track_caller]
+  pub fn assert_status(&self, status: StatusCode) -> &Self {
+    assert_eq!(self.status, status, "Unexpected status\n{self:?}");
+    self
+  }
+
+  #[track_caller]
+  pub fn assert_header(&self, name: &str, value: &str) -> &Self {
+    assert_eq!(
+      self.header(name),
+      Some(value),
+      "Unexpected header `{name}`\n{self:?}"
+    );
+    self
+  }
+
+  #[track_caller]
+  pub fn assert_text(&self, text: &str) -> &Self {
+    assert_eq!(self.text(), text, "Unexpected body\n{self:?}");
+    self
+  }
+
+  /// Asserts that the body is the JSON of `value`, whatever the formatting and the order of the
+  /// keys.
+  #[track_caller]
+  pub fn assert_json<T>(&self, value: &T) -> &Self
+  where
+    T: Serialize + ?Sized,
+  {
+    let expected = serde_json::to_value(value).expect("Failed to serialize the expected JSON");
+    assert_eq!(
+      self.json::<serde_json::Value>(),
+      expected,
+      "Unexpected JSON body\n{self:?}"
+    );
+    self
+  }
+}
+
+impl Debug for TestResponse {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", self.status)?;
+
+    for (name, value) in &self.headers {
+      writeln!(f, "{name}: {}", value.to_str().unwrap_or("<binary>"))?;
+    }
+
+    write!(f, "\n{}", self.text())
+  }
+}
+
+/// The name and value of a `Set-Cookie` header, `None` for the value when the cookie is removed.
+fn parse_set_cookie(header: &str) -> Option<(String, Option<String>)> {
+  let mut attributes = header.split(';').map(str::trim);
+  let (name, value) = attributes.next()?.split_once('=')?;
+
+  let removed = value.is_empty()
+    || attributes.any(|attribute| {
+      let attribute = attribute.to_ascii_lowercase();
+      attribute == "max-age=0" || attribute.starts_with("max-age=-")
+    });
+
+  Some((name.to_owned(), (!removed).then(|| value.to_owned())))
+}
+
+#[cfg(test)]
+mod test {
+  use axum::{
+    extract::State,
+    http::{header::COOKIE, HeaderMap, Method, StatusCode},
+    routing::{get, post},
+    Json,
+  };
+  use serde_json::{json, Value};
+
+  use super::{parse_set_cookie, TestApp};
+  use crate::{
+    axum::{Route, RouterError, Routes},
+    ControllerDetails, Module, ModuleDetails, Providers,
+  };
+
+  #[derive(Clone)]
+  struct AppState {
+    owner: &'static str,
+  }
+
+  /// A module with a single controller at `/todo`.
+  struct TodoModule(fn() -> Routes<AppState>);
+
+  impl Module for TodoModule {
+    type Context = ();
+    type ControllerContext = ();
+    type ControllerReturn = Routes<AppState>;
+
+    fn details(&self, _ctx: &mut ()) -> ModuleDetails<(), (), Routes<AppState>> {
+      let routes = self.0;
+
+      ModuleDetails {
+        imports: vec![],
+        controllers: vec![Box::new(move |_| ControllerDetails {
+          path: "/todo".into(),
+          return_: routes(),
+        })],
+        gateways: vec![],
+        providers: Providers::new(),
+      }
+    }
+  }
+
+  fn todo_routes() -> Routes<AppState> {
+    let create =
+      |State(state): State<AppState>, headers: HeaderMap, Json(mut todo): Json<Value>| async move {
+        todo["owner"] = state.owner.into();
+        todo["session"] = headers
+          .get(COOKIE)
+          .and_then(|cookie| cookie.to_str().ok())
+          .into();
+
+        (StatusCode::CREATED, [("x-todo-id", "1")], Json(todo))
+      };
+
+    Routes::new()
+      .route(Route::new("/", post(create)))
+      .route(Route::new(
+        "/login",
+        get(|| async { [("set-cookie", "session=abc; Path=/; HttpOnly")] }),
+      ))
+      .route(Route::new(
+        "/logout",
+        get(|| async { [("set-cookie", "session=; Max-Age=0")] }),
+      ))
+  }
+
+  #[tokio::test]
+  async fn module_tested_in_process() {
+    let app = TestApp::new(TodoModule(todo_routes)).with_state(AppState { owner: "ada" });
+
+    app
+      .post("/todo")
+      .json(&json!({ "title": "Write tests" }))
+      .await
+      .assert_status(StatusCode::CREATED)
+      .assert_header("x-todo-id", "1")
+      .assert_json(&json!({ "title": "Write tests", "owner": "ada", "session": null }));
+
+    app.get("/todo/login").await.assert_status(StatusCode::OK);
+    assert_eq!(app.cookie("session").as_deref(), Some("abc"));
+
+    app
+      .post("/todo")
+      .cookie("theme", "dark")
+      .json(&json!({}))
+      .await
+      .assert_json(&json!({ "owner": "ada", "session": "session=abc; theme=dark" }));
+
+    app.get("/todo/logout").await;
+    assert_eq!(app.cookie("session"), None);
+  }
+
+  #[test]
+  fn conflicts_returned_by_try_new() {
+    fn conflicting() -> Routes<AppState> {
+      Routes::new()
+        .route(Route::new("/", get(|| async {})).methods(&[Method::GET]))
+        .route(Route::new("/", get(|| async {})).methods(&[Method::GET]))
+    }
+
+    let err = TestApp::try_new(TodoModule(conflicting)).err().unwrap();
+    assert!(matches!(err, RouterError::Conflict(_)), "{err}");
+  }
+
+  #[test]
+  fn set_cookie_parsed() {
+    assert_eq!(
+      parse_set_cookie("session=abc; Path=/; HttpOnly"),
+      Some(("session".into(), Some("abc".into())))
+    );
+    assert_eq!(
+      parse_set_cookie("session=abc; Max-Age=0"),
+      Some(("session".into(), None))
+    );
+    assert_eq!(
+      parse_set_cookie("session=; Path=/"),
+      Some(("session".into(), None))
+    );
+    assert_eq!(parse_set_cookie("invalid"), None);
+  }
+}
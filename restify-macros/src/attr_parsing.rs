@@ -26,6 +26,46 @@ where
   Ok(())
 }
 
+pub(crate) fn parse_assignment_attribute<K, T>(
+  input: ParseStream,
+  out: &mut Option<(K, T)>,
+) -> syn::Result<()>
+where
+  K: Parse + ToTokens,
+  T: Parse,
+{
+  let kw = input.parse()?;
+  input.parse::<syn::Token![=]>()?;
+  let inner = input.parse()?;
+
+  if out.is_some() {
+    let kw_name = std::any::type_name::<K>().split("::").last().unwrap();
+    let msg = format!("`{kw_name}` specified more than once");
+    return Err(syn::Error::new_spanned(kw, msg));
+  }
+
+  *out = Some((kw, inner));
+
+  Ok(())
+}
+
+pub(crate) fn parse_flag_attribute<K>(input: ParseStream, out: &mut Option<K>) -> syn::Result<()>
+where
+  K: Parse + ToTokens,
+{
+  let kw = input.parse()?;
+
+  if out.is_some() {
+    let kw_name = std::any::type_name::<K>().split("::").last().unwrap();
+    let msg = format!("`{kw_name}` specified more than once");
+    return Err(syn::Error::new_spanned(kw, msg));
+  }
+
+  *out = Some(kw);
+
+  Ok(())
+}
+
 pub(crate) trait Combine: Sized {
   fn combine(self, other: Self) -> syn::Result<Self>;
 }
@@ -56,6 +96,21 @@ where
   Ok(())
 }
 
+pub(crate) fn combine_flag<K>(a: &mut Option<K>, b: Option<K>) -> syn::Result<()>
+where
+  K: ToTokens,
+{
+  if let Some(kw) = b {
+    if a.is_some() {
+      let kw_name = std::any::type_name::<K>().split("::").last().unwrap();
+      let msg = format!("`{kw_name}` specified more than once");
+      return Err(syn::Error::new_spanned(kw, msg));
+    }
+    *a = Some(kw);
+  }
+  Ok(())
+}
+
 pub(crate) fn second<T, K>(tuple: (T, K)) -> K {
   tuple.1
 }
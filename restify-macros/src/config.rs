@@ -1,49 +1,446 @@
-use std::{fs::read_to_string, path::PathBuf};
+use std::{
+  collections::BTreeMap,
+  env,
+  fs::read_to_string,
+  ops::Range,
+  path::{Path, PathBuf},
+};
 
 use lazy_static::lazy_static;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
 use serde::Deserialize;
-use syn::{parse_str, Type};
+use syn::{parse_str, Expr, Type};
+use toml::Spanned;
+
+use crate::route::{valid_header_name, valid_header_value};
 
 lazy_static! {
-  pub static ref CONFIG: Config = Config::new();
+  static ref CONFIG: Result<Config, String> = Config::load();
 }
 
-#[derive(Default, Debug, Deserialize)]
+/// The environment variable selecting a `[profiles.<name>]` of `restify.toml`.
+const PROFILE_VAR: &str = "RESTIFY_PROFILE";
+
+/// The environment variable setting the path of `restify.toml`, instead of looking it up.
+const CONFIG_VAR: &str = "RESTIFY_CONFIG";
+
+/// The configuration of `restify.toml`, or the error to report at the macro call site.
+pub fn config() -> syn::Result<&'static Config> {
+  CONFIG
+    .as_ref()
+    .map_err(|msg| syn::Error::new(Span::call_site(), msg))
+}
+
+/// Items making Cargo recompile the crate when `restify.toml`, its path or the profile changes.
+pub fn track() -> TokenStream {
+  let file = CONFIG
+    .as_ref()
+    .ok()
+    .and_then(|config| config.path.as_ref())
+    .map(|path| {
+      let path = path.to_string_lossy();
+      quote!(
+        const _: &[u8] = include_bytes!(#path);
+      )
+    });
+
+  quote! {
+    #file
+    const _: ::std::option::Option<&str> = option_env!(#PROFILE_VAR);
+    const _: ::std::option::Option<&str> = option_env!(#CONFIG_VAR);
+  }
+}
+
+#[derive(Default, Debug)]
 pub struct Config {
   pub state: Option<String>,
-  #[serde(rename = "module-context")]
   pub module_context: Option<String>,
+  pub controller: ControllerConfig,
+  pub routes: RoutesConfig,
+  pub views: ViewsConfig,
+  /// The directory of `restify.toml`, paths of the config are relative to it.
+  pub root: PathBuf,
+  /// The `restify.toml` in use, if any.
+  pub path: Option<PathBuf>,
+}
+
+/// `[controller]`, the defaults of every controller.
+#[derive(Default, Debug)]
+pub struct ControllerConfig {
+  /// Layers applied to every controller, around its own `wrap` layers.
+  pub wrap: Vec<String>,
+}
+
+/// `[routes]`, the defaults of every route.
+#[derive(Default, Debug)]
+pub struct RoutesConfig {
+  /// Headers set on every response, unless the handler sets them with `#[header]`.
+  pub headers: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+pub struct ViewsConfig {
+  /// The directory of the templates rendered by `#[render]`.
+  pub dir: String,
+}
+
+impl Default for ViewsConfig {
+  fn default() -> Self {
+    Self {
+      dir: "templates".into(),
+    }
+  }
+}
+
+/// The content of `restify.toml`, also the content of a profile.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct File {
+  state: Option<Spanned<String>>,
+  module_context: Option<Spanned<String>>,
+  controller: Option<ControllerFile>,
+  routes: Option<RoutesFile>,
+  views: Option<ViewsFile>,
+  #[serde(default)]
+  profiles: BTreeMap<String, File>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ControllerFile {
+  wrap: Option<Vec<Spanned<String>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoutesFile {
+  headers: Option<BTreeMap<Spanned<String>, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ViewsFile {
+  dir: Option<String>,
 }
 
 impl Config {
-  pub fn new() -> Self {
-    let path = match std::env::var("CARGO_MANIFEST_DIR") {
-      Ok(dir) if cfg!(feature = "cargo_manifest_dir") => {
-        let mut path = PathBuf::from(dir);
+  fn load() -> Result<Self, String> {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+
+    let (root, path) = match env::var_os(CONFIG_VAR).filter(|path| !path.is_empty()) {
+      Some(path) => {
+        let path = manifest_dir.unwrap_or_default().join(path);
+        let root = path.parent().map(Path::to_owned).unwrap_or_default();
+        (root, Some(path))
+      }
+      None => discover(manifest_dir),
+    };
+
+    let profile = env::var(PROFILE_VAR).ok().filter(|name| !name.is_empty());
+
+    Self::load_from(root, path, profile.as_deref())
+  }
+
+  /// Loads the config from `path`, with the keys of the profile `profile` if any.
+  fn load_from(
+    root: PathBuf,
+    path: Option<PathBuf>,
+    profile: Option<&str>,
+  ) -> Result<Self, String> {
+    let Some(path) = path else {
+      return Ok(Config {
+        root,
+        ..Config::default()
+      });
+    };
+
+    let content =
+      read_to_string(&path).map_err(|err| format!("Failed to read `{}`: {err}", path.display()))?;
+
+    let error = |span: Option<Range<usize>>, msg: &str| {
+      let (line, col) = span.map_or((1, 1), |span| line_col(&content, span.start));
+      format!("{}:{line}:{col}: {msg}", path.display())
+    };
+
+    let mut file: File =
+      toml::from_str(&content).map_err(|err| error(err.span(), err.message()))?;
+
+    let mut profiles = std::mem::take(&mut file.profiles);
+    let mut config = Config {
+      root,
+      path: Some(path.clone()),
+      ..Config::default()
+    };
+
+    config.apply(file, &error)?;
 
-        path.push("restify.toml");
+    if let Some(name) = profile {
+      let Some(profile) = profiles.remove(name) else {
+        let names: Vec<_> = profiles.keys().map(|name| format!("`{name}`")).collect();
+        return Err(format!(
+          "Unknown profile `{name}` in `{}` (from {PROFILE_VAR}), available: {}",
+          path.display(),
+          if names.is_empty() {
+            "none".into()
+          } else {
+            names.join(", ")
+          },
+        ));
+      };
 
-        path
+      if !profile.profiles.is_empty() {
+        let msg = format!("Profile `{name}` cannot contain profiles");
+        return Err(format!("{}: {msg}", path.display()));
       }
-      _ => PathBuf::from("restify.toml"),
+
+      config.apply(profile, &error)?;
+    }
+
+    Ok(config)
+  }
+
+  /// Overrides `self` with the keys set in `file`, validating them.
+  fn apply(
+    &mut self,
+    file: File,
+    error: &dyn Fn(Option<Range<usize>>, &str) -> String,
+  ) -> Result<(), String> {
+    let type_path = |value: Spanned<String>, key: &str| {
+      parse_str::<Type>(value.get_ref())
+        .map(|_| value.get_ref().clone())
+        .map_err(|_| error(Some(value.span()), &format!("`{key}` must be a type path")))
     };
 
-    if !path.exists() {
-      return Config::default();
+    if let Some(state) = file.state {
+      self.state = Some(type_path(state, "state")?);
+    }
+
+    if let Some(context) = file.module_context {
+      self.module_context = Some(type_path(context, "module-context")?);
     }
 
-    let config: Config =
-      toml::from_str(&read_to_string(path).expect("Failed to to read restify.toml"))
-        .expect("Failed to parse restify.toml");
+    if let Some(wrap) = file.controller.and_then(|c| c.wrap) {
+      self.controller.wrap = wrap
+        .into_iter()
+        .map(|layer| {
+          parse_str::<Expr>(layer.get_ref())
+            .map(|_| layer.get_ref().clone())
+            .map_err(|err| {
+              error(
+                Some(layer.span()),
+                &format!("Invalid layer expression: {err}"),
+              )
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    }
+
+    if let Some(headers) = file.routes.and_then(|r| r.headers) {
+      self.routes.headers = headers
+        .into_iter()
+        .map(|(name, value)| {
+          if !valid_header_name(name.get_ref()) {
+            return Err(error(Some(name.span()), "Invalid header name"));
+          }
 
-    if let Some(path) = &config.state {
-      parse_str::<Type>(path).expect("`state` must be a type path in restify.toml");
+          if !valid_header_value(&value) {
+            return Err(error(Some(name.span()), "Invalid header value"));
+          }
+
+          Ok((name.into_inner().to_ascii_lowercase(), value))
+        })
+        .collect::<Result<_, _>>()?;
     }
 
-    if let Some(path) = &config.module_context {
-      parse_str::<Type>(path).expect("`module-context` must be a type path in restify.toml");
+    if let Some(dir) = file.views.and_then(|v| v.dir) {
+      self.views.dir = dir;
     }
 
-    config
+    Ok(())
+  }
+}
+
+/// Finds `restify.toml` in the directory of the crate being compiled, or in its parents up to the
+/// root of its workspace. Returns the directory paths are relative to and the file, if any.
+fn discover(manifest_dir: Option<PathBuf>) -> (PathBuf, Option<PathBuf>) {
+  let Some(manifest_dir) = manifest_dir else {
+    let path = PathBuf::from("restify.toml");
+    return (PathBuf::new(), path.is_file().then_some(path));
+  };
+  let workspace_root = manifest_dir
+    .ancestors()
+    .find(|dir| is_workspace_root(dir))
+    .unwrap_or(&manifest_dir);
+
+  for dir in manifest_dir.ancestors() {
+    let path = dir.join("restify.toml");
+
+    if path.is_file() {
+      return (dir.to_owned(), Some(path));
+    }
+
+    if dir == workspace_root {
+      break;
+    }
+  }
+
+  (manifest_dir, None)
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+  read_to_string(dir.join("Cargo.toml"))
+    .ok()
+    .and_then(|content| content.parse::<toml::Table>().ok())
+    .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
+
+/// The 1-based line and column of the byte `offset` of `content`.
+fn line_col(content: &str, offset: usize) -> (usize, usize) {
+  let before = &content[..offset.min(content.len())];
+  let line = before.matches('\n').count() + 1;
+  let col = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+  (line, col)
+}
+
+#[cfg(test)]
+mod test {
+  use std::{
+    fs::{create_dir_all, remove_dir_all, write},
+    path::PathBuf,
+  };
+
+  use super::{discover, line_col, Config};
+
+  /// A directory of `files`, removed when dropped.
+  struct TempDir(PathBuf);
+
+  impl TempDir {
+    fn new(name: &str, files: &[(&str, &str)]) -> Self {
+      let dir = std::env::temp_dir().join(format!("restify-{name}-{}", std::process::id()));
+
+      for (path, content) in files {
+        let path = dir.join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, content).unwrap();
+      }
+
+      Self(dir)
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn found_in_parent_directories() {
+    let dir = TempDir::new(
+      "discover",
+      &[
+        ("restify.toml", ""),
+        ("app/Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]"),
+        ("app/restify.toml", ""),
+        ("app/crates/api/Cargo.toml", "[package]"),
+        ("app/crates/db/Cargo.toml", "[package]"),
+        ("app/crates/db/restify.toml", ""),
+        ("lib/Cargo.toml", "[package]"),
+      ],
+    );
+
+    let (root, path) = discover(Some(dir.0.join("app/crates/api")));
+    assert_eq!(root, dir.0.join("app"));
+    assert_eq!(path, Some(dir.0.join("app/restify.toml")));
+
+    let (root, path) = discover(Some(dir.0.join("app/crates/db")));
+    assert_eq!(root, dir.0.join("app/crates/db"));
+    assert_eq!(path, Some(dir.0.join("app/crates/db/restify.toml")));
+
+    // Outside of a workspace, only the crate directory
+    let (root, path) = discover(Some(dir.0.join("lib")));
+    assert_eq!(root, dir.0.join("lib"));
+    assert_eq!(path, None);
+  }
+
+  #[test]
+  fn profiles() {
+    let dir = TempDir::new(
+      "profiles",
+      &[(
+        "restify.toml",
+        r#"
+state = "crate::AppState"
+
+[views]
+dir = "views"
+
+[profiles.test]
+state = "crate::TestState"
+"#,
+      )],
+    );
+    let load =
+      |profile| Config::load_from(dir.0.clone(), Some(dir.0.join("restify.toml")), profile);
+
+    let config = load(None).unwrap();
+    assert_eq!(config.state.as_deref(), Some("crate::AppState"));
+    assert_eq!(config.views.dir, "views");
+
+    let config = load(Some("test")).unwrap();
+    assert_eq!(config.state.as_deref(), Some("crate::TestState"));
+    assert_eq!(config.views.dir, "views");
+
+    let error = load(Some("prod")).unwrap_err();
+    assert!(error.starts_with("Unknown profile `prod`"), "{error}");
+    assert!(
+      error.ends_with("(from RESTIFY_PROFILE), available: `test`"),
+      "{error}"
+    );
+  }
+
+  #[test]
+  fn errors_located() {
+    let dir = TempDir::new(
+      "errors",
+      &[
+        (
+          "unknown.toml",
+          "state = \"crate::AppState\"\n\n[views]\ndirs = \"views\"\n",
+        ),
+        (
+          "invalid.toml",
+          "[routes]\nheaders = { \"X Frame\" = \"DENY\" }\n",
+        ),
+      ],
+    );
+    let load = |file: &str| Config::load_from(dir.0.clone(), Some(dir.0.join(file)), None);
+
+    let error = load("unknown.toml").unwrap_err();
+    let path = dir.0.join("unknown.toml");
+    assert!(
+      error.starts_with(&format!("{}:4:1: unknown field `dirs`", path.display())),
+      "{error}"
+    );
+
+    let error = load("invalid.toml").unwrap_err();
+    let path = dir.0.join("invalid.toml");
+    assert_eq!(
+      error,
+      format!("{}:2:13: Invalid header name", path.display())
+    );
+  }
+
+  #[test]
+  fn line_and_column() {
+    let content = "a = 1\nb = \"é\"\n";
+
+    assert_eq!(line_col(content, 0), (1, 1));
+    assert_eq!(line_col(content, 4), (1, 5));
+    assert_eq!(line_col(content, 6), (2, 1));
+    assert_eq!(line_col(content, 13), (2, 7));
+    assert_eq!(line_col(content, 100), (3, 1));
   }
 }
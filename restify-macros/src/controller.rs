@@ -2,16 +2,22 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
   parse::{Parse, ParseStream},
-  parse2, parse_str,
+  parse2, parse_quote, parse_str,
   punctuated::Punctuated,
-  Attribute, Error, Expr, Generics, ImplItem, ItemImpl, LitStr, Meta, Token, Type,
+  Attribute, Error, Expr, Generics, Ident, ImplItem, ItemImpl, LitStr, Meta, Token, Type,
 };
 
-use crate::{config::CONFIG, route::Route};
+use crate::{
+  config::{config, track},
+  path,
+  route::{lint_routes, Route},
+};
 
 struct Controller {
   path: LitStr,
   state: Option<Type>,
+  /// The extractor of a singleton controller.
+  singleton: Option<Type>,
   wrappers: Vec<Expr>,
   attrs: Vec<Attribute>,
   type_: Type,
@@ -31,42 +37,93 @@ impl Controller {
     }: ItemImpl,
     args: Args,
   ) -> Result<Self, Error> {
-    let routes = items
-      .iter_mut()
-      .map(Route::new)
-      .collect::<Result<Vec<_>, Error>>()?
-      .into_iter()
-      .flatten()
-      .collect();
-
     let mut wrappers = vec![];
     let mut state = None::<Type>;
+    let mut version = None::<LitStr>;
+    let mut singleton = None::<Type>;
 
     for nv in args.options {
       if nv.path().is_ident("wrap") {
         wrappers.push(nv.require_name_value()?.value.clone());
       } else if nv.path().is_ident("state") {
         state = Some(parse2(nv.require_list()?.tokens.clone())?);
+      } else if nv.path().is_ident("singleton") {
+        singleton = Some(match &nv {
+          Meta::Path(_) => parse_quote!(::restify::axum::Singleton<Self>),
+          Meta::List(list) => {
+            let from: Ident = parse2(list.tokens.clone())?;
+
+            if from != "providers" {
+              return Err(syn::Error::new_spanned(
+                from,
+                "Expected `providers`, as in `singleton(providers)` for a controller built with \
+                 `FromProviders`",
+              ));
+            }
+
+            parse_quote!(::restify::axum::Singleton<Self, ::restify::axum::ViaProviders>)
+          }
+          Meta::NameValue(_) => return Err(syn::Error::new_spanned(nv, "Expected `singleton`")),
+        });
+      } else if nv.path().is_ident("version") {
+        let value = &nv.require_name_value()?.value;
+        if let Expr::Lit(syn::ExprLit {
+          lit: syn::Lit::Str(lit),
+          ..
+        }) = value
+        {
+          version = Some(lit.clone());
+        } else {
+          return Err(syn::Error::new_spanned(
+            value,
+            "Attribute version expects literal string",
+          ));
+        }
       } else {
         return Err(syn::Error::new_spanned(
           nv.path(),
-          "Unknown attribute key is specified; allowed: wrap",
+          "Unknown attribute key is specified; allowed: wrap, state, version and singleton",
         ));
       }
     }
 
-    if let Some(path) = &CONFIG.state {
+    path::parse(&args.path.value()).map_err(|msg| Error::new_spanned(&args.path, msg))?;
+
+    let mut routes: Vec<Route> = items
+      .iter_mut()
+      .map(|item| Route::new(item, singleton.as_ref(), &args.path.value()))
+      .collect::<Result<Vec<_>, Error>>()?
+      .into_iter()
+      .flatten()
+      .collect();
+
+    for route in routes.iter_mut() {
+      if route.method_args.version.is_none() {
+        route.method_args.version.clone_from(&version);
+      }
+    }
+
+    lint_routes(&routes)?;
+
+    let config = config()?;
+
+    if let Some(path) = &config.state {
       if state.is_none() {
         state = Some(parse_str(path)?)
       }
     }
 
+    for layer in &config.controller.wrap {
+      wrappers.push(parse_str(layer)?);
+    }
+
     Ok(Self {
       attrs,
       routes,
       path: args.path,
       items,
       state,
+      singleton,
       wrappers,
       generics,
       type_: *self_ty,
@@ -74,7 +131,7 @@ impl Controller {
   }
 }
 
-struct Args {
+pub(crate) struct Args {
   pub path: LitStr,
   pub options: Punctuated<Meta, Token![,]>,
 }
@@ -88,26 +145,33 @@ impl ToTokens for Controller {
       items,
       wrappers,
       state,
+      singleton,
       path,
       generics,
     } = self;
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let singleton = singleton
+      .as_ref()
+      .map(|_| quote!(.layer(::axum::Extension(::restify::axum::SingletonCell::<Self>::new()))));
+
     let controller = if cfg!(feature = "axum") {
       quote! {
         impl #impl_generics ::restify::Controller for #type_ #ty_generics #where_clause {
           type Context = ();
-          type Return = ::axum::Router<#state>;
+          type Return = ::restify::axum::Routes<#state>;
 
 
           fn configure(_ctx: &mut Self::Context) -> ::restify::ControllerDetails<Self::Return> {
-            use ::axum::{routing, Router};
+            use ::axum::routing;
             ::restify::ControllerDetails {
               path: #path.into(),
-              return_: Router::new()
-              #(.#routes)*
-              #(.layer(#wrappers))*,
+              return_: ::restify::axum::Routes::new()
+              #(.route(#routes))*
+              #(.layer(#wrappers))*
+              #singleton
+              .controller(::std::any::type_name::<Self>()),
             }
 
           }
@@ -117,6 +181,8 @@ impl ToTokens for Controller {
       quote!(compile_error!("Please unable adapter feature"))
     };
 
+    let track = track();
+
     let stream = quote! {
       impl #type_ {
         #(#items)*
@@ -124,6 +190,7 @@ impl ToTokens for Controller {
 
 
       #controller
+      #track
     };
 
     tokens.extend(stream);
@@ -195,7 +262,7 @@ pub fn expand(input: TokenStream, args: TokenStream) -> Result<TokenStream, Toke
 /// recover and show more precise errors within the macro body.
 ///
 /// See <https://github.com/rust-analyzer/rust-analyzer/issues/10468> for more info.
-fn input_and_compile_error(mut item: TokenStream, err: syn::Error) -> TokenStream {
+pub(crate) fn input_and_compile_error(mut item: TokenStream, err: syn::Error) -> TokenStream {
   item.extend(err.to_compile_error());
   item
 }
@@ -0,0 +1,327 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+  parse2, parse_str, spanned::Spanned, Error, Expr, FnArg, Generics, ImplItem, ItemImpl, LitStr,
+  ReturnType, Type,
+};
+
+use crate::{
+  config::{config, track},
+  controller::{input_and_compile_error, Args},
+};
+
+struct Gateway {
+  path: LitStr,
+  state: Option<Type>,
+  wrappers: Vec<Expr>,
+  type_: Type,
+  subscriptions: Vec<Subscription>,
+  items: Vec<ImplItem>,
+  generics: Generics,
+}
+
+/// A `#[subscribe("<event>")]` handler.
+struct Subscription {
+  event: LitStr,
+  name: syn::Ident,
+  /// The arguments passed to the handler, in order: the client for `&WsClient` arguments,
+  /// otherwise the data of the message.
+  args: Vec<TokenStream>,
+  data: Option<Type>,
+  reply: bool,
+}
+
+impl Subscription {
+  fn new(item: &mut ImplItem) -> Result<Option<Self>, Error> {
+    let ImplItem::Fn(item) = item else {
+      return Ok(None);
+    };
+
+    let Some(index) = item
+      .attrs
+      .iter()
+      .position(|attr| attr.path().is_ident("subscribe"))
+    else {
+      return Ok(None);
+    };
+
+    let attr = item.attrs.remove(index);
+    let event: LitStr = attr.parse_args()?;
+
+    if item
+      .attrs
+      .iter()
+      .any(|attr| attr.path().is_ident("subscribe"))
+    {
+      return Err(Error::new_spanned(
+        &item.sig.ident,
+        "A handler can subscribe to one event only",
+      ));
+    }
+
+    if item.sig.asyncness.is_none() {
+      return Err(Error::new_spanned(&item.sig, "Function must be async"));
+    }
+
+    let mut args = vec![];
+    let mut data = None;
+
+    for input in &item.sig.inputs {
+      match input {
+        FnArg::Receiver(rec) if rec.reference.is_some() && rec.mutability.is_none() => {}
+        FnArg::Receiver(rec) => {
+          return Err(Error::new(
+            rec.span(),
+            "Handlers of gateways must take `&self`",
+          ));
+        }
+        FnArg::Typed(arg) if matches!(*arg.ty, Type::Reference(_)) => {
+          if !is_ws_client(&arg.ty) {
+            return Err(Error::new_spanned(
+              &arg.ty,
+              "The only reference handlers of gateways take is `&WsClient`, the data of the \
+               message is owned",
+            ));
+          }
+
+          args.push(quote!(__client));
+        }
+        FnArg::Typed(arg) => {
+          if data.is_some() {
+            return Err(Error::new_spanned(
+              arg,
+              "Handlers of gateways take the data of the message as their only argument besides `&WsClient`",
+            ));
+          }
+
+          data = Some((*arg.ty).clone());
+          args.push(quote!(__data));
+        }
+      }
+    }
+
+    if !matches!(item.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+      return Err(Error::new_spanned(
+        &item.sig.ident,
+        "Handlers of gateways must take `&self`",
+      ));
+    }
+
+    Ok(Some(Self {
+      event,
+      name: item.sig.ident.clone(),
+      args,
+      data,
+      reply: !matches!(item.sig.output, ReturnType::Default),
+    }))
+  }
+}
+
+/// Whether `ty` is `&WsClient`.
+fn is_ws_client(ty: &Type) -> bool {
+  let Type::Reference(reference) = ty else {
+    return false;
+  };
+
+  match &*reference.elem {
+    Type::Path(path) => {
+      reference.mutability.is_none()
+        && path
+          .path
+          .segments
+          .last()
+          .is_some_and(|segment| segment.ident == "WsClient" && segment.arguments.is_empty())
+    }
+    _ => false,
+  }
+}
+
+impl ToTokens for Subscription {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let Subscription {
+      event,
+      name,
+      args,
+      data,
+      reply,
+    } = self;
+
+    let data = data
+      .iter()
+      .map(|ty| quote!(let __data = __message.data::<#ty>()?;));
+
+    let res = format_ident!("__res");
+    let reply = if *reply {
+      quote!(::restify::axum::ws_reply(#res))
+    } else {
+      quote!(::std::result::Result::Ok(::std::option::Option::None))
+    };
+
+    tokens.extend(quote! {
+      #event => {
+        #(#data)*
+        #[allow(clippy::let_unit_value)]
+        let #res = self.#name(#(#args),*).await;
+        #reply
+      }
+    });
+  }
+}
+
+impl Gateway {
+  fn new(
+    ItemImpl {
+      mut items,
+      self_ty,
+      generics,
+      ..
+    }: ItemImpl,
+    args: Args,
+  ) -> Result<Self, Error> {
+    let mut wrappers = vec![];
+    let mut state = None::<Type>;
+
+    for nv in args.options {
+      if nv.path().is_ident("wrap") {
+        wrappers.push(nv.require_name_value()?.value.clone());
+      } else if nv.path().is_ident("state") {
+        state = Some(parse2(nv.require_list()?.tokens.clone())?);
+      } else {
+        return Err(syn::Error::new_spanned(
+          nv.path(),
+          "Unknown attribute key is specified; allowed: wrap and state",
+        ));
+      }
+    }
+
+    let subscriptions: Vec<Subscription> = items
+      .iter_mut()
+      .map(Subscription::new)
+      .collect::<Result<Vec<_>, Error>>()?
+      .into_iter()
+      .flatten()
+      .collect();
+
+    for (i, subscription) in subscriptions.iter().enumerate() {
+      let event = subscription.event.value();
+
+      if subscriptions[..i].iter().any(|s| s.event.value() == event) {
+        return Err(Error::new_spanned(
+          &subscription.event,
+          format!("Event `{event}` is subscribed more than once"),
+        ));
+      }
+    }
+
+    if let Some(path) = &config()?.state {
+      if state.is_none() {
+        state = Some(parse_str(path)?)
+      }
+    }
+
+    Ok(Self {
+      path: args.path,
+      state,
+      wrappers,
+      type_: *self_ty,
+      subscriptions,
+      items,
+      generics,
+    })
+  }
+}
+
+impl ToTokens for Gateway {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let Gateway {
+      path,
+      state,
+      wrappers,
+      type_,
+      subscriptions,
+      items,
+      generics,
+    } = self;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let state_arg = state.as_ref().map_or_else(|| quote!(_), |s| quote!(#s));
+
+    let gateway = if cfg!(feature = "axum") {
+      quote! {
+        impl #impl_generics ::restify::Gateway for #type_ #ty_generics #where_clause {
+          type Context = ();
+          type Return = ::restify::axum::Routes<#state>;
+
+          fn configure(_ctx: &mut Self::Context) -> ::restify::ControllerDetails<Self::Return> {
+            ::restify::ControllerDetails {
+              path: #path.into(),
+              return_: ::restify::axum::Routes::new()
+                .route(
+                  ::restify::axum::Route::new(
+                    "/",
+                    ::restify::axum::gateway_route::<Self, #state_arg>(),
+                  )
+                  .methods(&[::axum::http::Method::GET]),
+                )
+                #(.layer(#wrappers))*
+                .controller(::std::any::type_name::<Self>()),
+            }
+          }
+        }
+
+        impl #impl_generics ::restify::axum::GatewayHandler for #type_ #ty_generics #where_clause {
+          fn handle<'__a>(
+            &'__a self,
+            __client: &'__a ::restify::axum::WsClient,
+            __message: &'__a ::restify::axum::WsMessage,
+          ) -> ::restify::axum::BoxWsFuture<'__a> {
+            ::std::boxed::Box::pin(async move {
+              match __message.event.as_str() {
+                #(#subscriptions)*
+                __event => ::std::result::Result::Err(
+                  ::restify::axum::WsError::UnknownEvent(__event.to_owned()),
+                ),
+              }
+            })
+          }
+        }
+      }
+    } else {
+      quote!(compile_error!("Please unable adapter feature"))
+    };
+
+    let track = track();
+
+    tokens.extend(quote! {
+      impl #impl_generics #type_ #ty_generics #where_clause {
+        #(#items)*
+      }
+
+      #gateway
+      #track
+    });
+  }
+}
+
+pub fn expand(input: TokenStream, args: TokenStream) -> Result<TokenStream, TokenStream> {
+  let item = parse2::<ItemImpl>(input.clone())
+    .and_then(|m| {
+      if m.trait_.is_some() {
+        return Err(syn::Error::new(
+          Span::call_site(),
+          r#"Unsupported impl Trait"#,
+        ));
+      }
+
+      Ok(m)
+    })
+    .map_err(|e| input_and_compile_error(input.clone(), e))?;
+  let args: Args = parse2(args).map_err(|e| input_and_compile_error(input.clone(), e))?;
+
+  Ok(
+    Gateway::new(item, args)
+      .map_err(Error::into_compile_error)?
+      .into_token_stream(),
+  )
+}
@@ -1,4 +1,9 @@
-use crate::attr_parsing::{combine_attribute, parse_parenthesized_attribute, Combine};
+use crate::attr_parsing::{
+  combine_attribute, combine_flag, parse_assignment_attribute, parse_flag_attribute,
+  parse_parenthesized_attribute, Combine,
+};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::{
   parse::{Parse, ParseStream},
   Token,
@@ -8,6 +13,13 @@ pub(crate) mod kw {
   syn::custom_keyword!(via);
   syn::custom_keyword!(rejection);
   syn::custom_keyword!(state);
+  syn::custom_keyword!(transient);
+  syn::custom_keyword!(factory);
+  syn::custom_keyword!(body);
+  syn::custom_keyword!(skip);
+  syn::custom_keyword!(default);
+  syn::custom_keyword!(optional);
+  syn::custom_keyword!(from_state);
 }
 
 #[derive(Default)]
@@ -15,6 +27,12 @@ pub(super) struct InjectableContainerAttrs {
   pub(super) via: Option<(kw::via, syn::Path)>,
   pub(super) rejection: Option<(kw::rejection, syn::Path)>,
   pub(super) state: Option<(kw::state, syn::Type)>,
+  /// Built on every extraction instead of once per request.
+  pub(super) transient: Option<kw::transient>,
+  /// An async function building the injectable from extractors, instead of its fields.
+  pub(super) factory: Option<(kw::factory, syn::Path)>,
+  /// Implements `FromRequest`, extracting the last field from the body.
+  pub(super) body: Option<kw::body>,
 }
 
 impl Parse for InjectableContainerAttrs {
@@ -22,6 +40,9 @@ impl Parse for InjectableContainerAttrs {
     let mut via = None;
     let mut rejection = None;
     let mut state = None;
+    let mut transient = None;
+    let mut factory = None;
+    let mut body = None;
 
     while !input.is_empty() {
       let lh = input.lookahead1();
@@ -31,6 +52,12 @@ impl Parse for InjectableContainerAttrs {
         parse_parenthesized_attribute(input, &mut rejection)?;
       } else if lh.peek(kw::state) {
         parse_parenthesized_attribute(input, &mut state)?;
+      } else if lh.peek(kw::transient) {
+        parse_flag_attribute(input, &mut transient)?;
+      } else if lh.peek(kw::factory) {
+        parse_assignment_attribute(input, &mut factory)?;
+      } else if lh.peek(kw::body) {
+        parse_flag_attribute(input, &mut body)?;
       } else {
         return Err(lh.error());
       }
@@ -42,6 +69,9 @@ impl Parse for InjectableContainerAttrs {
       via,
       rejection,
       state,
+      transient,
+      factory,
+      body,
     })
   }
 }
@@ -52,10 +82,16 @@ impl Combine for InjectableContainerAttrs {
       via,
       rejection,
       state,
+      transient,
+      factory,
+      body,
     } = other;
     combine_attribute(&mut self.via, via)?;
     combine_attribute(&mut self.rejection, rejection)?;
     combine_attribute(&mut self.state, state)?;
+    combine_flag(&mut self.transient, transient)?;
+    combine_attribute(&mut self.factory, factory)?;
+    combine_flag(&mut self.body, body)?;
     Ok(self)
   }
 }
@@ -63,16 +99,64 @@ impl Combine for InjectableContainerAttrs {
 #[derive(Default)]
 pub(super) struct InjectableFieldAttrs {
   pub(super) via: Option<(kw::via, syn::Path)>,
+  /// Not extracted, filled with `Default::default()`.
+  pub(super) skip: Option<kw::skip>,
+  /// Filled with `Default::default()` when the extractor rejects the request.
+  pub(super) default: Option<kw::default>,
+  /// An `Option<T>` field, `None` when the extractor of `T` rejects the request.
+  pub(super) optional: Option<kw::optional>,
+  /// The type the rejection of the field is converted into with `From`.
+  pub(super) rejection: Option<(kw::rejection, syn::Path)>,
+  /// Not extracted, taken from the state with `FromRef`.
+  pub(super) from_state: Option<kw::from_state>,
+}
+
+impl InjectableFieldAttrs {
+  /// The first option set on the field, for errors about options that can't be used.
+  pub(super) fn first_option(&self) -> Option<TokenStream> {
+    let Self {
+      via,
+      skip,
+      default,
+      optional,
+      rejection,
+      from_state,
+    } = self;
+
+    via
+      .as_ref()
+      .map(|(kw, _)| kw.to_token_stream())
+      .or_else(|| skip.as_ref().map(ToTokens::to_token_stream))
+      .or_else(|| default.as_ref().map(ToTokens::to_token_stream))
+      .or_else(|| optional.as_ref().map(ToTokens::to_token_stream))
+      .or_else(|| rejection.as_ref().map(|(kw, _)| kw.to_token_stream()))
+      .or_else(|| from_state.as_ref().map(ToTokens::to_token_stream))
+  }
 }
 
 impl Parse for InjectableFieldAttrs {
   fn parse(input: ParseStream) -> syn::Result<Self> {
     let mut via = None;
+    let mut skip = None;
+    let mut default = None;
+    let mut optional = None;
+    let mut rejection = None;
+    let mut from_state = None;
 
     while !input.is_empty() {
       let lh = input.lookahead1();
       if lh.peek(kw::via) {
         parse_parenthesized_attribute(input, &mut via)?;
+      } else if lh.peek(kw::skip) {
+        parse_flag_attribute(input, &mut skip)?;
+      } else if lh.peek(kw::default) {
+        parse_flag_attribute(input, &mut default)?;
+      } else if lh.peek(kw::optional) {
+        parse_flag_attribute(input, &mut optional)?;
+      } else if lh.peek(kw::rejection) {
+        parse_parenthesized_attribute(input, &mut rejection)?;
+      } else if lh.peek(kw::from_state) {
+        parse_flag_attribute(input, &mut from_state)?;
       } else {
         return Err(lh.error());
       }
@@ -80,14 +164,33 @@ impl Parse for InjectableFieldAttrs {
       let _ = input.parse::<Token![,]>();
     }
 
-    Ok(Self { via })
+    Ok(Self {
+      via,
+      skip,
+      default,
+      optional,
+      rejection,
+      from_state,
+    })
   }
 }
 
 impl Combine for InjectableFieldAttrs {
   fn combine(mut self, other: Self) -> syn::Result<Self> {
-    let Self { via } = other;
+    let Self {
+      via,
+      skip,
+      default,
+      optional,
+      rejection,
+      from_state,
+    } = other;
     combine_attribute(&mut self.via, via)?;
+    combine_flag(&mut self.skip, skip)?;
+    combine_flag(&mut self.default, default)?;
+    combine_flag(&mut self.optional, optional)?;
+    combine_attribute(&mut self.rejection, rejection)?;
+    combine_flag(&mut self.from_state, from_state)?;
     Ok(self)
   }
 }
@@ -1,17 +1,17 @@
 use super::{
   attr::{InjectableContainerAttrs, InjectableFieldAttrs},
-  error_on_generic_ident, parse_single_generic_type_on_struct,
+  parse_single_generic_type_on_struct,
 };
 use crate::{
   attr_parsing::{parse_attrs, second},
-  config::CONFIG,
+  config::config,
 };
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
 use std::{collections::HashSet, iter};
 use syn::{
-  parse_quote, parse_str, punctuated::Punctuated, spanned::Spanned, Fields, Ident, Path, Token,
-  Type,
+  parse_quote, parse_str, punctuated::Punctuated, spanned::Spanned, Fields, GenericArgument,
+  Generics, Ident, Path, PathArguments, Token, Type, WherePredicate,
 };
 
 #[derive(Debug)]
@@ -46,14 +46,16 @@ impl State {
     }
   }
 
+  /// The bounds of a generic state, the ones of the state of an axum `Router`, so that extractors
+  /// capturing the state like `Lazy` can be used.
   fn bounds(&self) -> TokenStream {
     match self {
       State::Custom(_) => quote! {},
       State::Default(inner) => quote! {
-          #inner: ::std::marker::Send + ::std::marker::Sync,
+          #inner: ::std::clone::Clone + ::std::marker::Send + ::std::marker::Sync + 'static,
       },
       State::CannotInfer => quote! {
-          S: ::std::marker::Send + ::std::marker::Sync,
+          S: ::std::clone::Clone + ::std::marker::Send + ::std::marker::Sync + 'static,
       },
     }
   }
@@ -81,18 +83,19 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
         struct_token: _,
       } = item;
 
-      let generic_ident = parse_single_generic_type_on_struct(generics, &fields)?;
-
       let InjectableContainerAttrs {
         via,
         rejection,
         state,
+        transient,
+        factory,
+        body,
       } = parse_attrs("injectable", &attrs)?;
 
       let state = match state {
         Some((_, state)) => State::Custom(state),
         None => {
-          if let Some(path) = &CONFIG.state {
+          if let Some(path) = &config()?.state {
             State::Custom(parse_str(path)?)
           } else {
             let mut inferred_state_types: HashSet<_> = infer_state_type_from_field_types(&fields)
@@ -104,7 +107,7 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
             }
 
             match inferred_state_types.len() {
-              0 => State::Default(syn::parse_quote!(S)),
+              0 => State::Default(state_param(&generics)),
               1 => State::Custom(inferred_state_types.iter().next().unwrap().to_owned()),
               _ => State::CannotInfer,
             }
@@ -112,19 +115,68 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
         }
       };
 
-      let trait_impl = match (via.map(second), rejection.map(second)) {
-        (Some(via), rejection) => impl_struct_by_extracting_all_at_once(
+      if let Some(body) = body {
+        if let Some((kw, _)) = &via {
+          return Err(syn::Error::new_spanned(
+            kw,
+            "`via` cannot be used together with `body`",
+          ));
+        }
+
+        if let Some((kw, _)) = &factory {
+          return Err(syn::Error::new_spanned(
+            kw,
+            "`factory` cannot be used together with `body`",
+          ));
+        }
+
+        if has_no_fields(&fields) {
+          return Err(syn::Error::new_spanned(
+            body,
+            "`body` requires a field extracted from the body, the last one",
+          ));
+        }
+      }
+
+      let trait_impl = match (factory, via.map(second), rejection.map(second)) {
+        (Some((factory_kw, _)), Some(_), _) => {
+          return Err(syn::Error::new_spanned(
+            factory_kw,
+            "`factory` cannot be used together with `via`",
+          ));
+        }
+        (Some((factory_kw, _)), _, Some(_)) => {
+          return Err(syn::Error::new_spanned(
+            factory_kw,
+            "`factory` cannot be used together with `rejection`, return the rejection from the factory",
+          ));
+        }
+        (Some((_, factory)), None, None) => {
+          impl_struct_by_factory(ident, &generics, factory, &state, transient.is_none())
+        }
+        (None, Some(via), rejection) => {
+          let generic_ident = parse_single_generic_type_on_struct(generics, &fields)?;
+          impl_struct_by_extracting_all_at_once(
+            ident,
+            fields,
+            via,
+            rejection,
+            generic_ident,
+            &state,
+            transient.is_none(),
+          )?
+        }
+        (None, None, rejection) if body.is_some() => {
+          impl_struct_by_extracting_body(ident, &generics, fields, rejection, &state)?
+        }
+        (None, None, rejection) => impl_struct_by_extracting_each_field(
           ident,
+          &generics,
           fields,
-          via,
           rejection,
-          generic_ident,
           &state,
+          transient.is_none(),
         )?,
-        (None, rejection) => {
-          error_on_generic_ident(generic_ident)?;
-          impl_struct_by_extracting_each_field(ident, fields, rejection, &state)?
-        }
       };
 
       if let State::CannotInfer = state {
@@ -156,7 +208,7 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
         variants,
       } = item;
 
-      let generics_error = format!("`#[derive(Injectable)] on enums don't support generics");
+      let generics_error = "`#[derive(Injectable)] on enums don't support generics";
 
       if !generics.params.is_empty() {
         return Err(syn::Error::new_spanned(generics, generics_error));
@@ -170,29 +222,69 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
         via,
         rejection,
         state,
+        transient,
+        factory,
+        body,
       } = parse_attrs("injectable", &attrs)?;
 
-      let state = match state {
-        Some((_, state)) => State::Custom(state),
-        None => (|| {
-          let via = via.as_ref().map(|(_, via)| via)?;
-          state_from_via(&ident, via).map(State::Custom)
-        })()
-        .unwrap_or_else(|| State::Default(syn::parse_quote!(S))),
-      };
+      if let Some(body) = body {
+        return Err(syn::Error::new_spanned(
+          body,
+          "`body` is only supported on structs",
+        ));
+      }
+
+      if let Some((factory_kw, _)) = factory {
+        return Err(syn::Error::new_spanned(
+          factory_kw,
+          "`factory` is only supported on structs",
+        ));
+      }
 
-      match (via.map(second), rejection) {
+      match (via.map(second), rejection.map(second)) {
         (Some(via), rejection) => {
-          impl_enum_by_extracting_all_at_once(ident, variants, via, rejection.map(second), state)
+          let state = match state {
+            Some((_, state)) => State::Custom(state),
+            None => state_from_via(&ident, &via)
+              .map(State::Custom)
+              .unwrap_or_else(|| State::Default(syn::parse_quote!(S))),
+          };
+
+          impl_enum_by_extracting_all_at_once(
+            ident,
+            variants,
+            via,
+            rejection,
+            state,
+            transient.is_none(),
+          )
+        }
+        (None, rejection) => {
+          let state = match state {
+            Some((_, state)) => State::Custom(state),
+            None => {
+              if let Some(path) = &config()?.state {
+                State::Custom(parse_str(path)?)
+              } else {
+                let inferred_state_types: HashSet<_> = variants
+                  .iter()
+                  .flat_map(|variant| {
+                    infer_state_type_from_field_types(&variant.fields)
+                      .chain(infer_state_type_from_field_attributes(&variant.fields))
+                  })
+                  .collect();
+
+                match inferred_state_types.len() {
+                  0 => State::Default(syn::parse_quote!(S)),
+                  1 => State::Custom(inferred_state_types.into_iter().next().unwrap()),
+                  _ => State::CannotInfer,
+                }
+              }
+            }
+          };
+
+          impl_enum_by_trying_each_variant(ident, variants, rejection, &state, transient.is_none())
         }
-        (None, Some((rejection_kw, _))) => Err(syn::Error::new_spanned(
-          rejection_kw,
-          "cannot use `rejection` without `via`",
-        )),
-        (None, _) => Err(syn::Error::new(
-          Span::call_site(),
-          "missing `#[injectable(via(...))]`",
-        )),
       }
     }
     _ => Err(syn::Error::new_spanned(item, "expected `struct` or `enum`")),
@@ -201,21 +293,31 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
 
 fn impl_struct_by_extracting_each_field(
   ident: syn::Ident,
+  generics: &Generics,
   fields: syn::Fields,
   rejection: Option<syn::Path>,
   state: &State,
+  cached: bool,
 ) -> syn::Result<TokenStream> {
-  let trait_fn_body = match state {
-    State::CannotInfer => quote! {
-        ::std::unimplemented!()
-    },
-    _ => {
-      let extract_fields = extract_fields(&fields, &rejection)?;
+  let (trait_fn_body, bounds) = match state {
+    State::CannotInfer => (
       quote! {
-          ::std::result::Result::Ok(Self {
-              #(#extract_fields)*
-          })
-      }
+          ::std::unimplemented!()
+      },
+      Vec::new(),
+    ),
+    _ => {
+      let (extract_fields, bounds) = extract_fields(&fields, &rejection, false, generics, state)?;
+      let extract = scoped(
+        quote! {
+            ::std::result::Result::<Self, Self::Rejection>::Ok(Self {
+                #(#extract_fields)*
+            })
+        },
+        true,
+        cached,
+      );
+      (extract, bounds)
     }
   };
 
@@ -227,25 +329,300 @@ fn impl_struct_by_extracting_each_field(
     quote!(::axum::response::Response)
   };
 
-  let impl_generics = state
-    .impl_generics()
-    .collect::<Punctuated<Type, Token![,]>>();
+  let generics_with_state = with_state(generics, state, bounds);
+  let (impl_generics, _, where_clause) = generics_with_state.split_for_impl();
+  let (_, ty_generics, _) = generics.split_for_impl();
 
   let trait_generics = state
     .trait_generics()
     .collect::<Punctuated<Type, Token![,]>>();
 
+  Ok(quote! {
+        #[::axum::async_trait]
+        #[automatically_derived]
+        impl #impl_generics ::axum::extract::FromRequestParts<#trait_generics> for #ident #ty_generics
+        #where_clause
+        {
+            type Rejection = #rejection_ident;
+
+            async fn from_request_parts(
+                parts: &mut ::axum::http::request::Parts,
+                state: &#state,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                #trait_fn_body
+            }
+        }
+  })
+}
+
+/// The generics of the impl for `generics`, the generics of the injectable: its parameters followed
+/// by the state parameter, and its where clause extended with the state bounds and `bounds`.
+fn with_state(generics: &Generics, state: &State, bounds: Vec<WherePredicate>) -> Generics {
+  let mut impl_generics = generics.clone();
+
+  for ty in state.impl_generics() {
+    impl_generics.params.push(parse_quote!(#ty));
+  }
+
   let state_bounds = state.bounds();
+  let state_bounds: Punctuated<WherePredicate, Token![,]> = parse_quote!(#state_bounds);
+
+  let where_clause = impl_generics.make_where_clause();
+  where_clause.predicates.extend(state_bounds);
+  where_clause.predicates.extend(bounds);
+
+  // Overrides and the request scope key injectables by `TypeId`, which requires `'static`.
+  if !generics.params.is_empty() {
+    where_clause.predicates.push(parse_quote!(Self: 'static));
+  }
+
+  impl_generics
+}
+
+/// The state parameter of an impl generic over the state, `S` unless the injectable already has a
+/// parameter named so.
+fn state_param(generics: &Generics) -> Type {
+  let taken = generics.type_params().any(|param| param.ident == "S");
+
+  if taken {
+    parse_quote!(__S)
+  } else {
+    parse_quote!(S)
+  }
+}
+
+/// Whether `ty` mentions one of the type parameters of `generics`, in which case the impl needs
+/// bounds for the extraction of the field.
+fn is_generic(ty: &Type, generics: &Generics) -> bool {
+  fn mentions(tokens: TokenStream, params: &HashSet<Ident>) -> bool {
+    tokens.into_iter().any(|token| match token {
+      TokenTree::Ident(ident) => params.contains(&ident),
+      TokenTree::Group(group) => mentions(group.stream(), params),
+      TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
+  }
+
+  let params: HashSet<_> = generics
+    .type_params()
+    .map(|param| param.ident.clone())
+    .collect();
+
+  !params.is_empty() && mentions(ty.to_token_stream(), &params)
+}
+
+/// Whether `ty` is a type of std or an extractor of axum, which doesn't depend on the state: it is
+/// extracted with any state, or isn't an extractor and must be an error where it is a field.
+fn is_any_state(ty: &Type) -> bool {
+  const ANY_STATE: &[&str] = &[
+    "bool",
+    "char",
+    "str",
+    "String",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "f32",
+    "f64",
+    "Vec",
+    "HashMap",
+    "Bytes",
+    "HeaderMap",
+    "Method",
+    "Uri",
+    "Version",
+    "Extensions",
+    "Path",
+    "Query",
+    "RawQuery",
+    "RawPathParams",
+    "Json",
+    "Form",
+    "Extension",
+    "ConnectInfo",
+    "MatchedPath",
+    "OriginalUri",
+    "NestedPath",
+    "Request",
+    "Multipart",
+    "WebSocketUpgrade",
+    "TypedHeader",
+    "Host",
+    "CookieJar",
+  ];
+
+  match ty {
+    Type::Reference(reference) => is_any_state(&reference.elem),
+    Type::Tuple(tuple) => tuple.elems.is_empty(),
+    Type::Path(path) if path.qself.is_none() => {
+      let Some(segment) = path.path.segments.last() else {
+        return false;
+      };
+
+      match &segment.arguments {
+        // `Option<T>` and `Result<T, E>` are extracted with `T`.
+        PathArguments::AngleBracketed(args)
+          if segment.ident == "Option" || segment.ident == "Result" =>
+        {
+          args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(is_any_state(ty)),
+            _ => None,
+          }) == Some(true)
+        }
+        _ => ANY_STATE.contains(&&*segment.ident.to_string()),
+      }
+    }
+    _ => false,
+  }
+}
+
+/// Wraps `extract`, the extraction of `Self` as a `Result`, with the lookup of the `Overrides` of
+/// the request and with the request-scoped cache, reused by the other extractors of the request.
+fn scoped(extract: TokenStream, overridable: bool, cached: bool) -> TokenStream {
+  let resolve_override = overridable.then(|| {
+    quote! {
+        if let ::std::option::Option::Some(value) = ::restify::axum::Overrides::resolve::<Self>(parts) {
+            return ::std::result::Result::Ok(value);
+        }
+    }
+  });
+
+  if !cached {
+    return quote! {
+        #resolve_override
+        #extract
+    };
+  }
+
+  quote! {
+      #resolve_override
+
+      use ::restify::axum::{CachedInScope as _, NotCachedInScope as _};
+
+      let scoped = ::restify::axum::Scoped::<Self>::new();
+
+      if let ::std::option::Option::Some(value) = (&scoped).scope_get(parts) {
+          return ::std::result::Result::Ok(value);
+      }
+
+      let value = #extract?;
+      (&scoped).scope_set(parts, &value);
+      ::std::result::Result::Ok(value)
+  }
+}
+
+fn impl_struct_by_extracting_body(
+  ident: syn::Ident,
+  generics: &Generics,
+  fields: syn::Fields,
+  rejection: Option<syn::Path>,
+  state: &State,
+) -> syn::Result<TokenStream> {
+  // The body can only be read once, so the injectable is never shared with the request scope.
+  let (trait_fn_body, bounds) = match state {
+    State::CannotInfer => (
+      quote! {
+          ::std::unimplemented!()
+      },
+      Vec::new(),
+    ),
+    _ => {
+      let (extract_fields, bounds) = extract_fields(&fields, &rejection, true, generics, state)?;
+      let extract = scoped(
+        quote! {
+            ::std::result::Result::Ok(Self {
+                #(#extract_fields)*
+            })
+        },
+        true,
+        false,
+      );
+
+      let body = quote! {
+          let (mut head, body) = req.into_parts();
+          let parts = &mut head;
+
+          #extract
+      };
+      (body, bounds)
+    }
+  };
+
+  let rejection_ident = match rejection {
+    Some(rejection) => quote!(#rejection),
+    None => quote!(::axum::response::Response),
+  };
+
+  let generics_with_state = with_state(generics, state, bounds);
+  let (impl_generics, _, where_clause) = generics_with_state.split_for_impl();
+  let (_, ty_generics, _) = generics.split_for_impl();
+
+  let trait_generics = state
+    .trait_generics()
+    .collect::<Punctuated<Type, Token![,]>>();
 
   Ok(quote! {
         #[::axum::async_trait]
         #[automatically_derived]
-        impl<#impl_generics> ::axum::extract::FromRequestParts<#trait_generics> for #ident
-        where
-            #state_bounds
+        impl #impl_generics ::axum::extract::FromRequest<#trait_generics> for #ident #ty_generics
+        #where_clause
         {
             type Rejection = #rejection_ident;
 
+            async fn from_request(
+                req: ::axum::extract::Request,
+                state: &#state,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                #trait_fn_body
+            }
+        }
+  })
+}
+
+fn impl_struct_by_factory(
+  ident: syn::Ident,
+  generics: &Generics,
+  factory: syn::Path,
+  state: &State,
+  cached: bool,
+) -> TokenStream {
+  let trait_fn_body = match state {
+    State::CannotInfer => quote! {
+        ::std::unimplemented!()
+    },
+    _ => scoped(
+      quote_spanned! {factory.span()=>
+          ::restify::axum::InjectableFactory::<#state, _, Self>::build(#factory, parts, state).await
+      },
+      true,
+      cached,
+    ),
+  };
+
+  let generics_with_state = with_state(generics, state, Vec::new());
+  let (impl_generics, _, where_clause) = generics_with_state.split_for_impl();
+  let (_, ty_generics, _) = generics.split_for_impl();
+
+  let trait_generics = state
+    .trait_generics()
+    .collect::<Punctuated<Type, Token![,]>>();
+
+  quote! {
+        #[::axum::async_trait]
+        #[automatically_derived]
+        impl #impl_generics ::axum::extract::FromRequestParts<#trait_generics> for #ident #ty_generics
+        #where_clause
+        {
+            type Rejection = ::axum::response::Response;
+
             async fn from_request_parts(
                 parts: &mut ::axum::http::request::Parts,
                 state: &#state,
@@ -253,7 +630,7 @@ fn impl_struct_by_extracting_each_field(
                 #trait_fn_body
             }
         }
-  })
+  }
 }
 
 fn has_no_fields(fields: &syn::Fields) -> bool {
@@ -264,10 +641,16 @@ fn has_no_fields(fields: &syn::Fields) -> bool {
   }
 }
 
+/// The initializers of the fields, extracted from `parts`, and the bounds of the fields whose type
+/// depends on `generics`. With `body`, the last field is extracted with `FromRequest` from `head`,
+/// the owned parts, and `body`.
 fn extract_fields(
   fields: &syn::Fields,
   rejection: &Option<syn::Path>,
-) -> syn::Result<Vec<TokenStream>> {
+  body: bool,
+  generics: &Generics,
+  state: &State,
+) -> syn::Result<(Vec<TokenStream>, Vec<WherePredicate>)> {
   fn member(field: &syn::Field, index: usize) -> TokenStream {
     match &field.ident {
       Some(ident) => quote! { #ident },
@@ -294,38 +677,211 @@ fn extract_fields(
     }
   }
 
-  let fields_iter = fields.iter();
+  let mut bounds = Vec::new();
 
-  let res: Vec<_> = fields_iter
+  let res: Vec<_> = fields
+    .iter()
     .enumerate()
     .map(|(index, field)| {
-      let InjectableFieldAttrs { via } = parse_attrs("injectable", &field.attrs)?;
+      let InjectableFieldAttrs {
+        via,
+        skip,
+        default,
+        optional,
+        rejection: field_rejection,
+        from_state,
+      } = parse_attrs("injectable", &field.attrs)?;
 
       let member = member(field, index);
-      let ty_span = field.ty.span();
+      let ty = &field.ty;
+      let ty_span = ty.span();
+      let is_body = body && index + 1 == fields.len();
+      let is_generic = is_generic(ty, generics);
+
+      if let Some(from_state) = from_state {
+        if via.is_some()
+          || skip.is_some()
+          || default.is_some()
+          || optional.is_some()
+          || field_rejection.is_some()
+        {
+          return Err(syn::Error::new_spanned(
+            from_state,
+            "`from_state` cannot be used together with other options",
+          ));
+        }
+
+        if is_body {
+          return Err(syn::Error::new_spanned(
+            from_state,
+            "The last field of a `body` injectable is extracted from the body, it cannot be taken \
+             from the state",
+          ));
+        }
+
+        // An injectable generic over the state works with any state containing the field.
+        if is_generic || matches!(state, State::Default(_)) {
+          bounds.push(parse_quote!(#ty: ::axum::extract::FromRef<#state>));
+        }
+
+        return Ok(quote_spanned! {ty_span=>
+            #member: <#ty as ::axum::extract::FromRef<#state>>::from_ref(state),
+        });
+      }
+
+      if let Some(skip) = skip {
+        if via.is_some() || default.is_some() || optional.is_some() || field_rejection.is_some() {
+          return Err(syn::Error::new_spanned(
+            skip,
+            "`skip` cannot be used together with other options",
+          ));
+        }
+
+        if is_body {
+          return Err(syn::Error::new_spanned(
+            skip,
+            "The last field of a `body` injectable is extracted from the body, it cannot be skipped",
+          ));
+        }
+
+        if is_generic {
+          bounds.push(parse_quote!(#ty: ::std::default::Default + ::std::marker::Send));
+        }
+
+        return Ok(quote_spanned! {ty_span=>
+            #member: ::std::default::Default::default(),
+        });
+      }
+
+      if let (Some(optional), Some(_)) = (&optional, &default) {
+        return Err(syn::Error::new_spanned(
+          optional,
+          "`optional` cannot be used together with `default`",
+        ));
+      }
+
+      if let (Some((kw, _)), true) = (&field_rejection, optional.is_some() || default.is_some()) {
+        return Err(syn::Error::new_spanned(
+          kw,
+          "`rejection` is never used by `optional` or `default` fields",
+        ));
+      }
+
+      if let Some(optional) = &optional {
+        if peel_option(&field.ty).is_none() {
+          return Err(syn::Error::new_spanned(
+            optional,
+            "`optional` requires a field of type `Option<T>`",
+          ));
+        }
+      }
+
+      // `via` used to extract the `T` of these fields, it now wraps the whole type.
+      if let (Some((kw, via)), None) = (&via, &optional) {
+        let via = via.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+
+        if peel_option(ty).is_some() {
+          let msg = format!(
+            "`via` on an `Option<T>` field would extract `{via}<Option<T>>`, add `optional` to \
+             extract `{via}<T>` and get `None` when it is rejected"
+          );
+          return Err(syn::Error::new_spanned(kw, msg));
+        }
+
+        if is_result(ty) {
+          let msg = format!(
+            "`via` on a `Result<T, E>` field would extract `{via}<Result<T, E>>`, remove `via` \
+             and use a `Result<{via}<T>, E>` field to get the rejection of `{via}<T>`"
+          );
+          return Err(syn::Error::new_spanned(kw, msg));
+        }
+      }
+
+      let inner = match &optional {
+        Some(_) => peel_option(ty).unwrap(),
+        None => ty,
+      };
+      let extracted: Type = match &via {
+        Some((_, via)) => parse_quote!(#via<#inner>),
+        None => inner.clone(),
+      };
+
+      // Fields of a generic type, and, when generic over the state, fields that may only be
+      // extracted with some states, e.g. injectables with `from_state` fields, as only the bounds
+      // tell that they work with it.
+      if is_generic || (matches!(state, State::Default(_)) && !is_any_state(&extracted)) {
+        let extractor: Path = if is_body {
+          parse_quote!(::axum::extract::FromRequest<#state>)
+        } else {
+          parse_quote!(::axum::extract::FromRequestParts<#state>)
+        };
+        let extractor_rejection = quote!(<#extracted as #extractor>::Rejection);
+
+        bounds.push(parse_quote!(#extracted: #extractor));
+        bounds.push(parse_quote!(#ty: ::std::marker::Send));
+
+        if default.is_some() {
+          bounds.push(parse_quote!(#ty: ::std::default::Default));
+        }
+
+        if optional.is_none() && default.is_none() {
+          match (&field_rejection, rejection) {
+            (Some((_, field_rejection)), Some(rejection)) => {
+              bounds.push(parse_quote!(
+                  #field_rejection: ::std::convert::From<#extractor_rejection>
+              ));
+              bounds.push(parse_quote!(#rejection: ::std::convert::From<#field_rejection>));
+            }
+            (Some((_, field_rejection)), None) => {
+              bounds.push(parse_quote!(
+                  #field_rejection: ::std::convert::From<#extractor_rejection>
+              ));
+              bounds.push(parse_quote!(#field_rejection: ::axum::response::IntoResponse));
+            }
+            (None, Some(rejection)) => {
+              bounds.push(parse_quote!(
+                  #rejection: ::std::convert::From<#extractor_rejection>
+              ));
+            }
+            (None, None) => {}
+          }
+        }
+      }
+
       let into_inner = into_inner(via, ty_span);
 
-      if peel_option(&field.ty).is_some() {
+      let extract = if is_body {
+        quote_spanned! {ty_span=>
+            ::axum::extract::FromRequest::from_request(
+                ::axum::extract::Request::from_parts(head, body),
+                state,
+            )
+        }
+      } else {
+        quote_spanned! {ty_span=>
+            ::axum::extract::FromRequestParts::from_request_parts(
+                parts,
+                state,
+            )
+        }
+      };
+
+      if optional.is_some() {
         Ok(quote_spanned! {ty_span=>
             #member: {
-                ::axum::extract::FromRequestParts::from_request_parts(
-                    parts,
-                    state,
-                )
+                #extract
                 .await
                 .ok()
                 .map(#into_inner)
             },
         })
-      } else if peel_result_ok(&field.ty).is_some() {
+      } else if default.is_some() {
         Ok(quote_spanned! {ty_span=>
             #member: {
-                ::axum::extract::FromRequestParts::from_request_parts(
-                    parts,
-                    state,
-                )
+                #extract
                 .await
                 .map(#into_inner)
+                .unwrap_or_default()
             },
         })
       } else {
@@ -335,14 +891,16 @@ fn extract_fields(
           quote! { ::axum::response::IntoResponse::into_response }
         };
 
+        let map_field_err = field_rejection.map(|(_, rejection)| {
+          quote! { .map_err(<#rejection as ::std::convert::From<_>>::from) }
+        });
+
         Ok(quote_spanned! {ty_span=>
             #member: {
-                ::axum::extract::FromRequestParts::from_request_parts(
-                    parts,
-                    state,
-                )
+                #extract
                 .await
                 .map(#into_inner)
+                #map_field_err
                 .map_err(#map_err)?
             },
         })
@@ -350,7 +908,18 @@ fn extract_fields(
     })
     .collect::<syn::Result<_>>()?;
 
-  Ok(res)
+  Ok((res, bounds))
+}
+
+fn is_result(ty: &syn::Type) -> bool {
+  match ty {
+    syn::Type::Path(type_path) => type_path
+      .path
+      .segments
+      .last()
+      .is_some_and(|segment| segment.ident == "Result"),
+    _ => false,
+  }
 }
 
 fn peel_option(ty: &syn::Type) -> Option<&syn::Type> {
@@ -384,37 +953,6 @@ fn peel_option(ty: &syn::Type) -> Option<&syn::Type> {
   }
 }
 
-fn peel_result_ok(ty: &syn::Type) -> Option<&syn::Type> {
-  let type_path = if let syn::Type::Path(type_path) = ty {
-    type_path
-  } else {
-    return None;
-  };
-
-  let segment = type_path.path.segments.last()?;
-
-  if segment.ident != "Result" {
-    return None;
-  }
-
-  let args = match &segment.arguments {
-    syn::PathArguments::AngleBracketed(args) => args,
-    syn::PathArguments::Parenthesized(_) | syn::PathArguments::None => return None,
-  };
-
-  let ty = if args.args.len() == 2 {
-    args.args.first().unwrap()
-  } else {
-    return None;
-  };
-
-  if let syn::GenericArgument::Type(ty) = ty {
-    Some(ty)
-  } else {
-    None
-  }
-}
-
 fn impl_struct_by_extracting_all_at_once(
   ident: syn::Ident,
   fields: syn::Fields,
@@ -422,6 +960,7 @@ fn impl_struct_by_extracting_all_at_once(
   rejection: Option<syn::Path>,
   generic_ident: Option<Ident>,
   state: &State,
+  cached: bool,
 ) -> syn::Result<TokenStream> {
   let fields = match fields {
     syn::Fields::Named(fields) => fields.named.into_iter(),
@@ -430,13 +969,13 @@ fn impl_struct_by_extracting_all_at_once(
   };
 
   for field in fields {
-    let InjectableFieldAttrs { via } = parse_attrs("injectable", &field.attrs)?;
+    let attrs: InjectableFieldAttrs = parse_attrs("injectable", &field.attrs)?;
 
-    if let Some((via, _)) = via {
+    if let Some(option) = attrs.first_option() {
       return Err(syn::Error::new_spanned(
-        via,
-        "`#[injectable(via(...))]` on a field cannot be used \
-                together with `#[injectable(...)]` on the container",
+        option,
+        "Field options cannot be used together with \
+                `#[injectable(via(...))]` on the container",
       ));
     }
   }
@@ -464,10 +1003,11 @@ fn impl_struct_by_extracting_all_at_once(
     .trait_generics()
     .collect::<Punctuated<Type, Token![,]>>();
 
-  let ident_generics = generic_ident
-    .is_some()
-    .then(|| quote! { <T> })
-    .unwrap_or_default();
+  let ident_generics = if generic_ident.is_some() {
+    quote! { <T> }
+  } else {
+    quote! {}
+  };
 
   let rejection_bound = rejection.as_ref().map(|rejection| {
       if generic_ident.is_some() {
@@ -497,6 +1037,18 @@ fn impl_struct_by_extracting_all_at_once(
 
   let state_bounds = state.bounds();
 
+  // `TypeId` requires `'static`, which a generic extractor can't assume.
+  let body = scoped(
+    quote! {
+        ::axum::extract::FromRequestParts::from_request_parts(parts, state)
+            .await
+            .map(|#via_path(value)| #value_to_self)
+            .map_err(#map_err)
+    },
+    generic_ident.is_none(),
+    cached && generic_ident.is_none(),
+  );
+
   let tokens = quote_spanned! {path_span=>
           #[::axum::async_trait]
           #[automatically_derived]
@@ -512,10 +1064,7 @@ fn impl_struct_by_extracting_all_at_once(
                   parts: &mut ::axum::http::request::Parts,
                   state: &#state,
               ) -> ::std::result::Result<Self, Self::Rejection> {
-                  ::axum::extract::FromRequestParts::from_request_parts(parts, state)
-                      .await
-                      .map(|#via_path(value)| #value_to_self)
-                      .map_err(#map_err)
+                  #body
               }
           }
   };
@@ -529,14 +1078,15 @@ fn impl_enum_by_extracting_all_at_once(
   path: syn::Path,
   rejection: Option<syn::Path>,
   state: State,
+  cached: bool,
 ) -> syn::Result<TokenStream> {
   for variant in variants {
-    let InjectableFieldAttrs { via } = parse_attrs("injectable", &variant.attrs)?;
+    let attrs: InjectableFieldAttrs = parse_attrs("injectable", &variant.attrs)?;
 
-    if let Some((via, _)) = via {
+    if let Some(option) = attrs.first_option() {
       return Err(syn::Error::new_spanned(
-        via,
-        "`#[injectable(via(...))]` cannot be used on variants",
+        option,
+        "Field options cannot be used on variants",
       ));
     }
 
@@ -547,11 +1097,12 @@ fn impl_enum_by_extracting_all_at_once(
     };
 
     for field in fields {
-      let InjectableFieldAttrs { via } = parse_attrs("injectable", &field.attrs)?;
-      if let Some((via, _)) = via {
+      let attrs: InjectableFieldAttrs = parse_attrs("injectable", &field.attrs)?;
+
+      if let Some(option) = attrs.first_option() {
         return Err(syn::Error::new_spanned(
-          via,
-          "`#[injectable(via(...))]` cannot be used inside variants",
+          option,
+          "Field options cannot be used inside variants",
         ));
       }
     }
@@ -570,6 +1121,16 @@ fn impl_enum_by_extracting_all_at_once(
   };
 
   let path_span = path.span();
+  let body = scoped(
+    quote! {
+        ::axum::extract::FromRequestParts::from_request_parts(parts, state)
+            .await
+            .map(|#path(inner)| inner)
+            .map_err(#map_err)
+    },
+    true,
+    cached,
+  );
 
   let impl_generics = state
     .impl_generics()
@@ -594,10 +1155,7 @@ fn impl_enum_by_extracting_all_at_once(
                   parts: &mut ::axum::http::request::Parts,
                   state: &#state,
               ) -> ::std::result::Result<Self, Self::Rejection> {
-                  ::axum::extract::FromRequestParts::from_request_parts(parts, state)
-                      .await
-                      .map(|#path(inner)| inner)
-                      .map_err(#map_err)
+                  #body
               }
           }
   };
@@ -605,6 +1163,131 @@ fn impl_enum_by_extracting_all_at_once(
   Ok(tokens)
 }
 
+/// Extracts the first variant whose fields are all extracted, in order. The rejection is the one of
+/// the last variant.
+fn impl_enum_by_trying_each_variant(
+  ident: syn::Ident,
+  variants: Punctuated<syn::Variant, Token![,]>,
+  rejection: Option<syn::Path>,
+  state: &State,
+  cached: bool,
+) -> syn::Result<TokenStream> {
+  if variants.is_empty() {
+    return Err(syn::Error::new_spanned(
+      ident,
+      "#[derive(Injectable)] on enums without `via` requires at least one variant",
+    ));
+  }
+
+  let mut attempts = Vec::new();
+  let mut bounds = Vec::new();
+
+  for (index, variant) in variants.iter().enumerate() {
+    let attrs: InjectableFieldAttrs = parse_attrs("injectable", &variant.attrs)?;
+
+    if let Some(option) = attrs.first_option() {
+      return Err(syn::Error::new_spanned(
+        option,
+        "Field options cannot be used on variants",
+      ));
+    }
+
+    if has_no_fields(&variant.fields) && index + 1 != variants.len() {
+      return Err(syn::Error::new_spanned(
+        variant,
+        "A variant without fields is always extracted, so the following variants would never be \
+         tried. Move it last",
+      ));
+    }
+
+    let variant_ident = &variant.ident;
+    let (extract_fields, variant_bounds) = extract_fields(
+      &variant.fields,
+      &rejection,
+      false,
+      &Generics::default(),
+      state,
+    )?;
+    bounds.extend(variant_bounds);
+
+    attempts.push(quote! {
+        async {
+            ::std::result::Result::<Self, Self::Rejection>::Ok(Self::#variant_ident {
+                #(#extract_fields)*
+            })
+        }
+        .await
+    });
+  }
+
+  let (last, attempts) = attempts.split_last().unwrap();
+
+  let trait_fn_body = match state {
+    State::CannotInfer => quote! {
+        ::std::unimplemented!()
+    },
+    _ => scoped(
+      quote! {
+          async {
+              #(
+                  if let ::std::result::Result::Ok(value) = #attempts {
+                      return ::std::result::Result::Ok(value);
+                  }
+              )*
+              #last
+          }
+          .await
+      },
+      true,
+      cached,
+    ),
+  };
+
+  let rejection_ident = match rejection {
+    Some(rejection) => quote!(#rejection),
+    None => quote!(::axum::response::Response),
+  };
+
+  let generics_with_state = with_state(&Generics::default(), state, bounds);
+  let (impl_generics, _, where_clause) = generics_with_state.split_for_impl();
+
+  let trait_generics = state
+    .trait_generics()
+    .collect::<Punctuated<Type, Token![,]>>();
+
+  let tokens = quote! {
+        #[::axum::async_trait]
+        #[automatically_derived]
+        impl #impl_generics ::axum::extract::FromRequestParts<#trait_generics> for #ident
+        #where_clause
+        {
+            type Rejection = #rejection_ident;
+
+            async fn from_request_parts(
+                parts: &mut ::axum::http::request::Parts,
+                state: &#state,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                #trait_fn_body
+            }
+        }
+  };
+
+  if let State::CannotInfer = state {
+    let compile_error = syn::Error::new(
+      Span::call_site(),
+      "can't infer state type, please add `#[injectable(state = MyStateType)]` attribute",
+    )
+    .into_compile_error();
+
+    Ok(quote! {
+        #tokens
+        #compile_error
+    })
+  } else {
+    Ok(tokens)
+  }
+}
+
 /// For a struct like
 ///
 /// ```skip
@@ -643,24 +1326,32 @@ fn infer_state_type_from_field_attributes(fields: &Fields) -> impl Iterator<Item
       Box::new(fields_named.named.iter().filter_map(|field| {
         // TODO(david): its a little wasteful to parse the attributes again here
         // ideally we should parse things once and pass the data down
-        let InjectableFieldAttrs { via } = parse_attrs("injectable", &field.attrs).ok()?;
-        let (_, via_path) = via?;
-        path_ident_is_state(&via_path).then(|| field.ty.clone())
+        state_type_from_field_attributes(field)
       })) as Box<dyn Iterator<Item = Type>>
     }
     Fields::Unnamed(fields_unnamed) => {
       Box::new(fields_unnamed.unnamed.iter().filter_map(|field| {
         // TODO(david): its a little wasteful to parse the attributes again here
         // ideally we should parse things once and pass the data down
-        let InjectableFieldAttrs { via } = parse_attrs("injectable", &field.attrs).ok()?;
-        let (_, via_path) = via?;
-        path_ident_is_state(&via_path).then(|| field.ty.clone())
+        state_type_from_field_attributes(field)
       }))
     }
     Fields::Unit => Box::new(iter::empty()),
   }
 }
 
+fn state_type_from_field_attributes(field: &syn::Field) -> Option<Type> {
+  let InjectableFieldAttrs { via, optional, .. } = parse_attrs("injectable", &field.attrs).ok()?;
+  let (_, via_path) = via?;
+
+  let ty = match optional {
+    Some(_) => peel_option(&field.ty)?,
+    None => &field.ty,
+  };
+
+  path_ident_is_state(&via_path).then(|| ty.clone())
+}
+
 fn path_ident_is_state(path: &Path) -> bool {
   if let Some(last_segment) = path.segments.last() {
     last_segment.ident == "State"
@@ -15,6 +15,8 @@ pub(crate) fn expand(item: syn::Item) -> syn::Result<TokenStream> {
   }
 }
 
+/// The generic type of a struct extracted with `#[injectable(via(...))]`, wrapped by the `via`
+/// extractor instead of the struct itself.
 pub fn parse_single_generic_type_on_struct(
   generics: syn::Generics,
   fields: &syn::Fields,
@@ -22,7 +24,7 @@ pub fn parse_single_generic_type_on_struct(
   if let Some(where_clause) = generics.where_clause {
     return Err(syn::Error::new_spanned(
       where_clause,
-      format_args!("#[derive(Injectable)] doesn't support structs with `where` clauses"),
+      format_args!("#[derive(Injectable)] doesn't support `where` clauses together with `via`"),
     ));
   }
 
@@ -37,7 +39,7 @@ pub fn parse_single_generic_type_on_struct(
             lifetime,
             format_args!(
               "#[derive(Injectable)] doesn't support structs \
-                             that are generic over lifetimes"
+                             that are generic over lifetimes together with `via`"
             ),
           ));
         }
@@ -46,7 +48,7 @@ pub fn parse_single_generic_type_on_struct(
             konst,
             format_args!(
               "#[derive(Injectable)] doesn't support structs \
-                             that have const generics"
+                             that have const generics together with `via`"
             ),
           ));
         }
@@ -58,7 +60,7 @@ pub fn parse_single_generic_type_on_struct(
             fields_named,
             format_args!(
               "#[derive(Injectable)] doesn't support named fields \
-                             for generic structs. Use a tuple struct instead"
+                             for generic structs with `via`. Use a tuple struct instead"
             ),
           ));
         }
@@ -79,7 +81,7 @@ pub fn parse_single_generic_type_on_struct(
             if type_path
               .path
               .get_ident()
-              .map_or(true, |field_type_ident| field_type_ident != ty_ident)
+              .is_none_or(|field_type_ident| field_type_ident != ty_ident)
             {
               return Err(syn::Error::new_spanned(
                 type_path,
@@ -100,20 +102,9 @@ pub fn parse_single_generic_type_on_struct(
     }
     _ => Err(syn::Error::new_spanned(
       generics,
-      format_args!("#[derive(Injectable)] only supports 0 or 1 generic type parameters"),
-    )),
-  }
-}
-
-pub fn error_on_generic_ident(generic_ident: Option<Ident>) -> syn::Result<()> {
-  if let Some(generic_ident) = generic_ident {
-    Err(syn::Error::new_spanned(
-      generic_ident,
       format_args!(
-        "#[derive(Injectable)] only supports generics when used with #[injectable(via)]"
+        "#[derive(Injectable)] only supports 0 or 1 generic type parameters together with `via`"
       ),
-    ))
-  } else {
-    Ok(())
+    )),
   }
 }
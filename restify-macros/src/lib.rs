@@ -1,8 +1,10 @@
 mod attr_parsing;
 mod config;
 mod controller;
+mod gateway;
 mod injectable;
 mod module;
+mod path;
 mod route;
 
 use proc_macro::TokenStream;
@@ -15,12 +17,19 @@ pub fn controller(args: TokenStream, input: TokenStream) -> TokenStream {
     .into()
 }
 
+#[proc_macro_attribute]
+pub fn gateway(args: TokenStream, input: TokenStream) -> TokenStream {
+  gateway::expand(input.into(), args.into())
+    .unwrap_or_else(|e| e)
+    .into()
+}
+
 #[proc_macro_derive(Injectable, attributes(injectable))]
 pub fn injectable(item: TokenStream) -> TokenStream {
   let item = parse_macro_input!(item as syn::Item);
 
   injectable::expand(item)
-    .unwrap_or_else(|e| e.into_compile_error().into())
+    .unwrap_or_else(|e| e.into_compile_error())
     .into()
 }
 
@@ -28,9 +37,7 @@ pub fn injectable(item: TokenStream) -> TokenStream {
 pub fn module(item: TokenStream) -> TokenStream {
   let item = parse_macro_input!(item as DeriveInput);
 
-  module::expand(item)
-    .unwrap_or_else(|e| e.into_compile_error().into())
-    .into()
+  module::expand(item).unwrap_or_else(|e| e.into_compile_error().into())
 }
 
 fn infer_state_types<'a, I>(types: I) -> impl Iterator<Item = Type> + 'a
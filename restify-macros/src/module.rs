@@ -3,7 +3,7 @@ use proc_macro2::Span;
 use quote::quote;
 use syn::{parenthesized, parse::Parse, parse_str, DeriveInput, Expr, Token, Type};
 
-use crate::config::CONFIG;
+use crate::config::{config, track};
 
 pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
   let ident = &item.ident;
@@ -11,6 +11,8 @@ pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
   let mut imports: Vec<Expr> = vec![];
   let mut middlewares: Vec<Expr> = vec![];
   let mut controllers: Vec<Expr> = vec![];
+  let mut gateways: Vec<Expr> = vec![];
+  let mut health: Vec<Expr> = vec![];
   let mut state = None::<Type>;
   let mut context = None::<Type>;
 
@@ -45,6 +47,23 @@ pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
         controllers.extend(i);
       }
 
+      if meta.path.is_ident("gateways") {
+        let content;
+        parenthesized!(content in meta.input);
+
+        let i = content.parse_terminated(Expr::parse, Token![,])?;
+        gateways.extend(i);
+      }
+
+      // #[module(health(Indicator, Indicator))]
+      if meta.path.is_ident("health") {
+        let content;
+        parenthesized!(content in meta.input);
+
+        let i = content.parse_terminated(Expr::parse, Token![,])?;
+        health.extend(i);
+      }
+
       if meta.path.is_ident("state") {
         let content;
         parenthesized!(content in meta.input);
@@ -61,13 +80,15 @@ pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
     })?;
   }
 
-  if let Some(path) = &CONFIG.state {
+  let config = config()?;
+
+  if let Some(path) = &config.state {
     if state.is_none() {
       state = Some(parse_str(path)?)
     }
   }
 
-  if let Some(path) = &CONFIG.module_context {
+  if let Some(path) = &config.module_context {
     if context.is_none() {
       context = Some(parse_str(path)?)
     }
@@ -84,7 +105,7 @@ pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
   let module_context = context.map_or_else(|| quote!(()), |c| quote!(#c));
 
   let return_content = if cfg!(feature = "axum") {
-    quote!(::axum::routing::Router<#state>)
+    quote!(::restify::axum::Routes<#state>)
   } else {
     return Err(syn::Error::new(
       Span::call_site(),
@@ -92,6 +113,22 @@ pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
     ));
   };
 
+  // The health indicators, and the clients of the gateways to emit to them with `WsServer`.
+  let providers = if health.is_empty() && gateways.is_empty() {
+    quote!(::restify::Providers::new())
+  } else {
+    let state = state.as_ref().map_or_else(|| quote!(()), |s| quote!(#s));
+
+    quote!({
+      let mut providers = ::restify::Providers::new();
+      #(providers.add(::restify::axum::RegisteredIndicator::<#state>::readiness(#health));)*
+      #(providers.insert(::restify::axum::WsServer::<#gateways>::new());)*
+      providers
+    })
+  };
+
+  let track = track();
+
   let module = quote! {
     impl #impl_generics Module for #ident #ty_generics #where_clause {
       type Context = #module_context;
@@ -104,10 +141,14 @@ pub fn expand(item: DeriveInput) -> Result<TokenStream, syn::Error> {
 
         ::restify::ModuleDetails {
           imports: vec![#(Box::new(#imports)),*],
-          controllers: vec![#(Box::new(<#controllers as ::restify::Controller>::configure)),*]
+          controllers: vec![#(Box::new(<#controllers as ::restify::Controller>::configure)),*],
+          gateways: vec![#(Box::new(<#gateways as ::restify::Gateway>::configure)),*],
+          providers: #providers,
         }
       }
     }
+
+    #track
   };
 
   Ok(module.into())
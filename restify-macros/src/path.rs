@@ -0,0 +1,171 @@
+/// A segment of a path template: `{id}` or `{id:uuid}` is a parameter and `{*path}` a wildcard,
+/// axum's `:id` and `*path` are accepted too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+  Static(String),
+  Param(String),
+  Wildcard(String),
+}
+
+impl Segment {
+  pub fn name(&self) -> Option<&str> {
+    match self {
+      Segment::Static(_) => None,
+      Segment::Param(name) | Segment::Wildcard(name) => Some(name),
+    }
+  }
+}
+
+impl std::fmt::Display for Segment {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Segment::Static(s) => f.write_str(s),
+      Segment::Param(name) => write!(f, "{{{name}}}"),
+      Segment::Wildcard(name) => write!(f, "{{*{name}}}"),
+    }
+  }
+}
+
+/// Parses the segments of `path`, with the same rules as `restify::path::PathTemplate`. An empty
+/// path is the root of its controller.
+pub fn parse(path: &str) -> Result<Vec<Segment>, String> {
+  if path.is_empty() {
+    return Ok(vec![]);
+  }
+
+  let Some(rest) = path.strip_prefix('/') else {
+    return Err(format!("Path must start with `/`, e.g. `/{path}`"));
+  };
+
+  let mut segments: Vec<Segment> = vec![];
+
+  for segment in rest.split('/') {
+    if let Some(Segment::Wildcard(name)) = segments.last() {
+      return Err(format!("Wildcard `{{*{name}}}` must be the last segment"));
+    }
+
+    let segment = parse_segment(segment)?;
+
+    if let Some(name) = segment.name() {
+      if segments.iter().any(|s| s.name() == Some(name)) {
+        return Err(format!("Parameter `{name}` is defined more than once"));
+      }
+    }
+
+    segments.push(segment);
+  }
+
+  Ok(segments)
+}
+
+/// The names of the parameters of `segments`, in order.
+pub fn params(segments: &[Segment]) -> impl Iterator<Item = &str> {
+  segments.iter().filter_map(Segment::name)
+}
+
+fn parse_segment(segment: &str) -> Result<Segment, String> {
+  let name = |name: &str| {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+      Ok(name.to_owned())
+    } else {
+      Err(format!(
+        "Invalid parameter name `{name}`, expected letters, digits and `_`"
+      ))
+    }
+  };
+
+  if let Some(inner) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+    if let Some(wildcard) = inner.strip_prefix('*') {
+      return Ok(Segment::Wildcard(name(wildcard)?));
+    }
+
+    let (param, kind) = inner.split_once(':').unwrap_or((inner, "string"));
+
+    if !["string", "int", "uint", "uuid"].contains(&kind) {
+      return Err(format!(
+        "Unknown parameter type `{kind}`, expected `string`, `int`, `uint` or `uuid`"
+      ));
+    }
+
+    return Ok(Segment::Param(name(param)?));
+  }
+
+  if segment.contains(['{', '}']) {
+    return Err(format!(
+      "Invalid segment `{segment}`, parameters must be whole segments like `{{id}}`"
+    ));
+  }
+
+  if let Some(param) = segment.strip_prefix(':') {
+    return Ok(Segment::Param(name(param)?));
+  }
+
+  if let Some(wildcard) = segment.strip_prefix('*') {
+    return Ok(Segment::Wildcard(name(wildcard)?));
+  }
+
+  Ok(Segment::Static(segment.to_owned()))
+}
+
+/// Joins a controller path and a route path the same way `axum::Router::nest` does.
+pub fn join(prefix: &str, path: &str) -> String {
+  let prefix = prefix.trim_start_matches('/');
+  let path = path.trim_start_matches('/');
+
+  match (prefix.is_empty(), path.is_empty()) {
+    (true, _) => format!("/{path}"),
+    (false, true) => format!("/{prefix}"),
+    (false, false) => format!("/{}/{path}", prefix.trim_end_matches('/')),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{join, parse, Segment};
+
+  /// The cases of `restify::path`, so both sides agree on which paths are valid.
+  #[test]
+  fn shared_cases() {
+    let cases = include_str!("../../restify-core/src/path_cases.txt")
+      .lines()
+      .filter(|line| !line.is_empty() && !line.starts_with('#'))
+      .map(|line| line.split_once(" => ").expect("`<input> => <expected>`"));
+
+    for (input, expected) in cases {
+      let actual = match input.split_once(" + ") {
+        Some((prefix, path)) => join(empty(prefix), empty(path)),
+        None => match parse(input) {
+          Ok(segments) => to_axum(&segments),
+          Err(err) => format!("! {err}"),
+        },
+      };
+
+      match expected.strip_prefix("! ") {
+        Some(error) => assert!(
+          actual.starts_with("! ") && actual.contains(error),
+          "`{input}`: {actual}"
+        ),
+        None => assert_eq!(actual, expected, "`{input}`"),
+      }
+    }
+  }
+
+  fn to_axum(segments: &[Segment]) -> String {
+    segments
+      .iter()
+      .map(|segment| match segment {
+        Segment::Static(s) => format!("/{s}"),
+        Segment::Param(name) => format!("/:{name}"),
+        Segment::Wildcard(name) => format!("/*{name}"),
+      })
+      .collect()
+  }
+
+  fn empty(value: &str) -> &str {
+    if value == "\"\"" {
+      ""
+    } else {
+      value
+    }
+  }
+}
@@ -0,0 +1,112 @@
+use syn::Error;
+
+use super::{Methods, Route};
+use crate::path::Segment;
+
+/// Checks the routes of one controller against each other: duplicate method and path pairs,
+/// conflicting parameters and wildcards, and paths that don't agree on a trailing slash. Errors
+/// point at the path of the later route.
+pub fn lint_routes(routes: &[Route]) -> syn::Result<()> {
+  let mut trailing_slash = None::<&Route>;
+
+  for (i, route) in routes.iter().enumerate() {
+    let path = route.method_args.path.value();
+    let error = |msg: String| Error::new_spanned(&route.method_args.path, msg);
+
+    if path.len() > 1 {
+      match trailing_slash {
+        None => trailing_slash = Some(route),
+        Some(first) if first.method_args.path.value().ends_with('/') != path.ends_with('/') => {
+          return Err(error(format!(
+            "Trailing slash inconsistent with `{}` of `{}`, the paths of a controller either all end with `/` or none does",
+            first.method_args.path.value(),
+            first.name
+          )));
+        }
+        Some(_) => {}
+      }
+    }
+
+    for other in &routes[..i] {
+      let version = |route: &Route| route.method_args.version.as_ref().map(|v| v.value());
+
+      if version(route) != version(other) {
+        continue;
+      }
+
+      match conflict(segments(other), segments(route)) {
+        Some(Conflict::Same) => {
+          if let Some(method) =
+            shared_method(&other.method_args.methods, &route.method_args.methods)
+          {
+            return Err(error(format!(
+              "Duplicate route `{method} {path}`, already handled by `{}`",
+              other.name
+            )));
+          }
+        }
+        Some(Conflict::Captures(a, b)) => {
+          return Err(error(format!(
+            "`{b}` conflicts with `{a}` of `{}`, routes can't have a wildcard and a parameter, or parameters of different names, at the same position",
+            other.name
+          )));
+        }
+        None => {}
+      }
+    }
+  }
+
+  Ok(())
+}
+
+enum Conflict<'a> {
+  /// The paths are the same.
+  Same,
+  /// The captures of both paths at the first position they differ, which the router rejects.
+  Captures(&'a Segment, &'a Segment),
+}
+
+/// The segments of the route path, `[""]` for the root.
+fn segments(route: &Route) -> &[Segment] {
+  const ROOT: &[Segment] = &[Segment::Static(String::new())];
+
+  match &route.segments[..] {
+    [] => ROOT,
+    segments => segments,
+  }
+}
+
+/// Whether axum rejects the two paths, mirroring the check of `IntoRouter`.
+fn conflict<'a>(a: &'a [Segment], b: &'a [Segment]) -> Option<Conflict<'a>> {
+  let Some(i) = a.iter().zip(b).position(|(a, b)| a != b) else {
+    return (a.len() == b.len()).then_some(Conflict::Same);
+  };
+
+  match (&a[i], &b[i]) {
+    (Segment::Wildcard(_), Segment::Param(_) | Segment::Wildcard(_))
+    | (Segment::Param(_), Segment::Wildcard(_)) => Some(Conflict::Captures(&a[i], &b[i])),
+    (Segment::Param(_), Segment::Param(_))
+      if a.len() == b.len()
+        && a.iter().zip(b).all(|pair| match pair {
+          (Segment::Param(_), Segment::Param(_)) => true,
+          (a, b) => a == b,
+        }) =>
+    {
+      Some(Conflict::Captures(&a[i], &b[i]))
+    }
+    _ => None,
+  }
+}
+
+/// A method handled by both routes, `*` when both handle any method. A route of any method
+/// doesn't conflict with the routes of specific methods, which take precedence over it.
+fn shared_method(a: &Methods, b: &Methods) -> Option<String> {
+  match (a, b) {
+    (Methods::Any, Methods::Any) => Some("*".into()),
+    (Methods::Any, Methods::Only(_)) | (Methods::Only(_), Methods::Any) => None,
+    (Methods::Only(a), Methods::Only(b)) => a
+      .iter()
+      .find(|method| b.contains(method))
+      .map(ToString::to_string),
+  }
+}
@@ -1,13 +1,27 @@
 #![allow(dead_code)]
 
+mod lint;
+mod params;
+mod response;
+
+use crate::{
+  config::config,
+  path::{self, Segment},
+};
 use proc_macro2::Span;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
+
+pub use lint::lint_routes;
+use params::check_path_extractors;
+use response::ResponseArgs;
+pub use response::{valid_header_name, valid_header_value};
 use syn::{
   parse::{Parse, ParseStream},
   parse2,
   punctuated::Punctuated,
   spanned::Spanned,
   Attribute, Error, FnArg, Ident, ImplItem, LitStr, Meta, MetaNameValue, Path, Signature, Token,
+  Type,
 };
 
 pub struct Route {
@@ -16,60 +30,126 @@ pub struct Route {
   pub sig: Signature,
   pub method_args: MethodArgs,
   pub attrs: Vec<Attribute>,
+  /// The extractor of the singleton controller the handler takes `&self` from.
+  pub singleton: Option<Type>,
+  pub response: ResponseArgs,
+  /// The segments of the route path, relative to the controller.
+  pub segments: Vec<Segment>,
 }
 
 impl Route {
-  pub fn new(item: &mut ImplItem) -> Result<Option<Self>, Error> {
+  /// `prefix` is the path of the controller, `singleton` the extractor of a singleton controller.
+  pub fn new(
+    item: &mut ImplItem,
+    singleton: Option<&Type>,
+    prefix: &str,
+  ) -> Result<Option<Self>, Error> {
     let ImplItem::Fn(item) = item else {
       return Ok(None);
     };
 
     let name = item.sig.ident.clone();
 
-    if let Some(input) = item.sig.inputs.first() {
-      if matches!(input, FnArg::Receiver(rec) if rec.reference.is_some()) {
-        return Err(Error::new(
-          input.span(),
-          "Method receiver cannot be reference",
-        ));
-      }
-    }
-
     let mut method_args = None;
     let mut attrs = vec![];
     let mut descriptions: Vec<Attribute> = vec![];
 
-    for i in 0..item.attrs.len() {
-      let attr = item.attrs[i].clone();
-
-      if let Ok(m) = MethodType::from_path(attr.path()) {
+    let mut response = ResponseArgs::default();
+
+    for attr in std::mem::take(&mut item.attrs) {
+      let methods = if let Ok(m) = MethodType::from_path(attr.path()) {
+        Some(Some(Methods::Only(vec![MethodTypeExt::Standard(m)])))
+      } else if attr.path().is_ident("any") {
+        Some(Some(Methods::Any))
+      } else if attr.path().is_ident("sse") {
+        response.sse = true;
+        Some(Some(Methods::Only(vec![MethodTypeExt::Standard(
+          MethodType::Get,
+        )])))
+      } else if attr.path().is_ident("route") {
+        Some(None)
+      } else {
+        None
+      };
+
+      if let Some(methods) = methods {
         if method_args.is_some() {
           return Err(syn::Error::new(
             attr.span(),
-            r#"Unsupported to use more than one method"#,
+            r#"Unsupported to use more than one method attribute, use #[route("<path>", method = "GET", method = "HEAD")] instead"#,
           ));
         }
 
-        method_args = Some(MethodArgs::new(attr.meta, m)?);
+        method_args = Some(MethodArgs::new(attr.meta, methods)?);
 
-        item.attrs.remove(i);
+        continue;
+      }
 
+      if response.parse_attr(&attr)? {
         continue;
       }
 
+      item.attrs.push(attr.clone());
+
       if attr.path().is_ident("doc") {
         descriptions.push(attr);
         continue;
       }
 
-      attrs.push(attr.clone());
+      attrs.push(attr);
     }
 
     let Some(method_args) = method_args else {
+      if !response.is_empty() {
+        return Err(syn::Error::new_spanned(
+          &item.sig.ident,
+          "Attributes status, header, return_json and render require a method attribute, e.g. #[get]",
+        ));
+      }
+
       return Ok(None);
     };
 
-    if matches!(item.sig.output, syn::ReturnType::Default) {
+    response.apply_defaults(&config()?.routes);
+
+    if response.sse && (response.return_json || response.render.is_some()) {
+      return Err(syn::Error::new_spanned(
+        &item.sig.ident,
+        "Handlers of #[sse] return a stream, they cannot use return_json or render",
+      ));
+    }
+
+    let mut by_ref = None;
+
+    if let Some(FnArg::Receiver(rec)) = item.sig.inputs.first() {
+      match (singleton.is_some(), &rec.reference, &rec.mutability) {
+        (false, Some(_), _) => {
+          return Err(Error::new(
+            rec.span(),
+            r#"Method receiver cannot be reference, use #[controller("<path>", singleton)] for `&self` handlers"#,
+          ));
+        }
+        (true, Some(_), Some(_)) => {
+          return Err(Error::new(
+            rec.span(),
+            "Handlers of singleton controllers cannot take `&mut self`",
+          ));
+        }
+        (true, None, _) => {
+          return Err(Error::new(
+            rec.span(),
+            "Handlers of singleton controllers must take `&self`",
+          ));
+        }
+        (true, Some(_), None) => by_ref = singleton.cloned(),
+        (false, None, _) => {}
+      }
+    }
+
+    if matches!(item.sig.output, syn::ReturnType::Default)
+      && !response.return_json
+      && response.render.is_none()
+    {
       return Err(syn::Error::new_spanned(
         item,
         "Function has no return type. Cannot be used as handler (You can return no type if you add #[return_json] attribute",
@@ -80,14 +160,76 @@ impl Route {
       return Err(syn::Error::new_spanned(item, "Function must be async"));
     }
 
+    let path = method_args.path.value();
+    let segments = path::parse(&path).map_err(|msg| Error::new_spanned(&method_args.path, msg))?;
+    let joined = path::join(prefix, &path);
+    let joined = path::parse(&joined).map_err(|msg| {
+      let msg = format!("{msg} in `{joined}`, the path joined with the controller path");
+      Error::new_spanned(&method_args.path, msg)
+    })?;
+    let params: Vec<_> = path::params(&joined).map(str::to_owned).collect();
+    check_path_extractors(&item.sig, &params)?;
+
     Ok(Some(Self {
       method_args,
       descriptions,
       sig: item.sig.clone(),
       attrs,
       name,
+      singleton: by_ref,
+      response,
+      segments,
     }))
   }
+
+  /// The handler passed to the adapter: the method itself, or a closure calling it when the
+  /// singleton controller has to be extracted for `&self` or the response has to be shaped.
+  fn handler(&self) -> proc_macro2::TokenStream {
+    let name = &self.name;
+
+    if self.singleton.is_none() && self.response.is_empty() {
+      return quote!(Self::#name);
+    }
+
+    let (args, types): (Vec<_>, Vec<_>) = self
+      .sig
+      .inputs
+      .iter()
+      .filter_map(|input| match input {
+        FnArg::Typed(arg) => Some(&arg.ty),
+        FnArg::Receiver(_) => None,
+      })
+      .enumerate()
+      .map(|(i, ty)| (format_ident!("__arg{}", i), ty))
+      .unzip();
+
+    let has_receiver = matches!(self.sig.inputs.first(), Some(FnArg::Receiver(_)));
+
+    let (this, call) = match (has_receiver, &self.singleton) {
+      (true, Some(singleton)) => (
+        quote!(__this: #singleton,),
+        quote!(__this.#name(#(#args),*)),
+      ),
+      (true, None) => (quote!(__this: Self,), quote!(__this.#name(#(#args),*))),
+      (false, _) => (quote!(), quote!(Self::#name(#(#args),*))),
+    };
+
+    let views = format_ident!("__views");
+    let this = match &self.response.render {
+      Some(_) => quote!(#this #views: ::restify::axum::Views,),
+      None => this,
+    };
+
+    let res = self
+      .response
+      .shape(quote!(#call.await), &self.sig.output, &views);
+
+    quote! {
+      |#this #(#args: #types),*| async move {
+        #res
+      }
+    }
+  }
 }
 
 impl ToTokens for Route {
@@ -97,23 +239,37 @@ impl ToTokens for Route {
         MethodArgs {
           path,
           guards: _,
-          method,
+          methods,
           wrappers,
+          version,
           ..
         },
-      name,
       ..
     } = self;
 
-    let method = Ident::new(method.as_lower_str(), Span::call_site());
+    let version = version.iter();
+    let handler = self.handler();
 
     let stream = if cfg!(feature = "axum") {
+      let (method_router, methods) = match methods {
+        Methods::Any => (quote!(routing::any(#handler)), quote!(.any_method())),
+        Methods::Only(methods) => (
+          quote!(::restify::axum::on_methods(&[#(#methods),*], #handler)),
+          quote!(.methods(&[#(#methods),*])),
+        ),
+      };
+
+      let name = self.name.to_string();
+
       quote! {
-        route(
+        ::restify::axum::Route::new(
           #path,
-          routing::#method(Self::#name)
+          #method_router
           #(.layer(#wrappers))*
         )
+        #methods
+        .handler(#name)
+        #(.version(#version))*
       }
     } else {
       quote!(compile_error!(
@@ -164,16 +320,26 @@ pub struct MethodArgs {
   pub resource_name: Option<syn::LitStr>,
   pub guards: Vec<Path>,
   pub wrappers: Vec<syn::Expr>,
-  pub method: MethodType,
+  pub version: Option<syn::LitStr>,
+  pub methods: Methods,
+}
+
+/// The methods a handler is routed for, `None` in [`MethodArgs::new`] means they are given with
+/// `method = "..."` options of `#[route]`.
+pub enum Methods {
+  Any,
+  Only(Vec<MethodTypeExt>),
 }
 
 impl MethodArgs {
-  fn new(meta: Meta, method: MethodType) -> syn::Result<Self> {
+  fn new(meta: Meta, mut methods: Option<Methods>) -> syn::Result<Self> {
+    let is_route = methods.is_none();
     let span = meta.span();
     let mut path = None;
     let mut resource_name = None;
     let mut guards = Vec::new();
     let mut wrappers = Vec::new();
+    let mut version = None;
 
     match meta {
       Meta::Path(_) => {}
@@ -208,23 +374,70 @@ impl MethodArgs {
             }
           } else if nv.path.is_ident("wrap") {
             wrappers.push(nv.value);
+          } else if nv.path.is_ident("method") && is_route {
+            let syn::Expr::Lit(syn::ExprLit {
+              lit: syn::Lit::Str(lit),
+              ..
+            }) = nv.value
+            else {
+              return Err(syn::Error::new_spanned(
+                nv.value,
+                "Attribute method expects literal string",
+              ));
+            };
+
+            let method = MethodTypeExt::try_from(&lit)?;
+
+            match &mut methods {
+              Some(Methods::Only(methods)) if methods.contains(&method) => {
+                let msg = format!("HTTP method defined more than once: `{}`", lit.value());
+                return Err(syn::Error::new_spanned(lit, msg));
+              }
+              Some(Methods::Only(methods)) => methods.push(method),
+              _ => methods = Some(Methods::Only(vec![method])),
+            }
+          } else if nv.path.is_ident("version") {
+            if let syn::Expr::Lit(syn::ExprLit {
+              lit: syn::Lit::Str(lit),
+              ..
+            }) = nv.value
+            {
+              version = Some(lit);
+            } else {
+              return Err(syn::Error::new_spanned(
+                nv.value,
+                "Attribute version expects literal string",
+              ));
+            }
           } else {
             return Err(syn::Error::new_spanned(
               nv.path,
-              "Unknown attribute key is specified; allowed: guard and wrap",
+              if is_route {
+                "Unknown attribute key is specified; allowed: guard, wrap, version and method"
+              } else {
+                "Unknown attribute key is specified; allowed: guard, wrap and version"
+              },
             ));
           }
         }
       }
     }
 
+    let Some(methods) = methods else {
+      return Err(syn::Error::new(
+        span,
+        r#"#[route] requires at least one method, e.g. #[route("<path>", method = "GET")]"#,
+      ));
+    };
+
     Ok(Self {
       path: path
         .unwrap_or_else(|| LitStr::new(if cfg!(feature = "axum") { "/" } else { "" }, span)),
       resource_name,
       guards,
       wrappers,
-      method,
+      version,
+      methods,
     })
   }
 }
@@ -242,24 +455,18 @@ macro_rules! standard_method_type {
       }
 
       impl MethodType {
-          // fn as_str(&self) -> &'static str {
-          //     match self {
-          //         $(Self::$variant => stringify!($variant),)+
-          //     }
-          // }
-
-          fn as_lower_str(&self) -> &'static str {
+          fn as_upper_str(&self) -> &'static str {
             match self {
-                $(Self::$variant => stringify!($lower),)+
+                $(Self::$variant => stringify!($upper),)+
             }
           }
 
-          // fn parse(method: &str) -> Result<Self, String> {
-          //     match method {
-          //         $(stringify!($upper) => Ok(Self::$variant),)+
-          //         _ => Err(format!("HTTP method must be uppercase: `{}`", method)),
-          //     }
-          // }
+          fn parse(method: &str) -> Result<Self, String> {
+              match method {
+                  $(stringify!($upper) => Ok(Self::$variant),)+
+                  _ => Err(format!("HTTP method must be uppercase: `{}`", method)),
+              }
+          }
 
           fn from_path(method: &Path) -> Result<Self, ()> {
               match () {
@@ -282,3 +489,70 @@ standard_method_type! {
   Trace,     TRACE,   trace,
   Patch,     PATCH,   patch,
 }
+
+#[derive(Clone)]
+pub enum MethodTypeExt {
+  Standard(MethodType),
+  Custom(LitStr),
+}
+
+impl PartialEq for MethodTypeExt {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::Standard(a), Self::Standard(b)) => a == b,
+      (Self::Custom(a), Self::Custom(b)) => a.value() == b.value(),
+      _ => false,
+    }
+  }
+}
+
+impl std::fmt::Display for MethodTypeExt {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Standard(method) => f.write_str(method.as_upper_str()),
+      Self::Custom(method) => f.write_str(&method.value()),
+    }
+  }
+}
+
+impl TryFrom<&LitStr> for MethodTypeExt {
+  type Error = syn::Error;
+
+  fn try_from(value: &LitStr) -> Result<Self, Self::Error> {
+    let method = value.value();
+
+    // The token characters of RFC 7230, as accepted by `http::Method`.
+    let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+
+    match MethodType::parse(&method) {
+      Ok(method) => Ok(Self::Standard(method)),
+      // `get` is most likely a typo for `GET`, not an extension method.
+      Err(err) if MethodType::parse(&method.to_ascii_uppercase()).is_ok() => {
+        Err(syn::Error::new_spanned(value, err))
+      }
+      Err(_) if !method.is_empty() && method.chars().all(is_token) => {
+        Ok(Self::Custom(value.clone()))
+      }
+      Err(_) => Err(syn::Error::new_spanned(
+        value,
+        format!("`{method}` is not a valid HTTP method"),
+      )),
+    }
+  }
+}
+
+impl ToTokens for MethodTypeExt {
+  fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+    let stream = match self {
+      MethodTypeExt::Standard(method) => {
+        let method = Ident::new(method.as_upper_str(), Span::call_site());
+        quote!(::axum::http::Method::#method)
+      }
+      MethodTypeExt::Custom(method) => quote! {
+        ::axum::http::Method::from_bytes(#method.as_bytes()).expect("valid HTTP method")
+      },
+    };
+
+    tokens.extend(stream);
+  }
+}
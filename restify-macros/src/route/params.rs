@@ -0,0 +1,165 @@
+use syn::{
+  spanned::Spanned, Error, FnArg, GenericArgument, Member, Pat, PathArguments, Signature, Type,
+};
+
+/// Checks that the `Path` extractors of `sig` match `params`, the parameters of the controller and
+/// route paths: a tuple of the same size, a single value for a single parameter, or a struct whose
+/// destructured fields are parameters.
+pub fn check_path_extractors(sig: &Signature, params: &[String]) -> syn::Result<()> {
+  for input in &sig.inputs {
+    let FnArg::Typed(arg) = input else {
+      continue;
+    };
+
+    let Some(ty) = path_extractor(&arg.ty) else {
+      continue;
+    };
+
+    let expected = match params {
+      [] => "no path parameters".to_owned(),
+      [param] => format!("1 path parameter (`{param}`)"),
+      params => format!(
+        "{} path parameters ({})",
+        params.len(),
+        params
+          .iter()
+          .map(|param| format!("`{param}`"))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    };
+
+    if params.is_empty() {
+      return Err(Error::new(
+        arg.span(),
+        format!("`Path` extractor but the route has {expected}"),
+      ));
+    }
+
+    match peel(ty) {
+      Type::Tuple(tuple) if tuple.elems.len() != params.len() => {
+        let msg = format!(
+          "`Path` extracts a tuple of {} values but the route has {expected}",
+          tuple.elems.len()
+        );
+        return Err(Error::new(tuple.span(), msg));
+      }
+      Type::Tuple(_) => {}
+      ty if is_scalar(ty) && params.len() != 1 => {
+        let msg = format!(
+          "`Path` extracts a single value but the route has {expected}, use a tuple or a struct"
+        );
+        return Err(Error::new(ty.span(), msg));
+      }
+      ty => check_struct_pattern(&arg.pat, ty, params)?,
+    }
+  }
+
+  Ok(())
+}
+
+/// `T` of a `Path<T>` type.
+fn path_extractor(ty: &Type) -> Option<&Type> {
+  let Type::Path(path) = peel(ty) else {
+    return None;
+  };
+
+  let segment = path.path.segments.last()?;
+
+  if segment.ident != "Path" {
+    return None;
+  }
+
+  let PathArguments::AngleBracketed(args) = &segment.arguments else {
+    return None;
+  };
+
+  match args.args.iter().collect::<Vec<_>>()[..] {
+    [GenericArgument::Type(ty)] => Some(ty),
+    _ => None,
+  }
+}
+
+fn peel(ty: &Type) -> &Type {
+  match ty {
+    Type::Paren(paren) => peel(&paren.elem),
+    Type::Group(group) => peel(&group.elem),
+    ty => ty,
+  }
+}
+
+/// Types deserialized from a single path parameter.
+fn is_scalar(ty: &Type) -> bool {
+  const SCALARS: &[&str] = &[
+    "bool", "char", "str", "String", "Uuid", "u8", "u16", "u32", "u64", "u128", "usize", "i8",
+    "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+  ];
+
+  match ty {
+    Type::Reference(reference) => is_scalar(&reference.elem),
+    Type::Path(path) if path.qself.is_none() => path.path.segments.last().is_some_and(|segment| {
+      segment.arguments.is_empty() && SCALARS.contains(&&*segment.ident.to_string())
+    }),
+    _ => false,
+  }
+}
+
+/// The name of a type that may be deserialized from the path parameters by name: not generic, and
+/// not one of the maps of all the parameters.
+fn struct_name(ty: &Type) -> Option<String> {
+  const MAPS: &[&str] = &["HashMap", "BTreeMap", "IndexMap", "Vec"];
+
+  let Type::Path(path) = ty else {
+    return None;
+  };
+
+  let segment = path.path.segments.last()?;
+  let name = segment.ident.to_string();
+
+  (path.qself.is_none() && segment.arguments.is_empty() && !MAPS.contains(&&*name)).then_some(name)
+}
+
+/// Checks the fields of `Path(Params { id, .. }): Path<Params>` against `params`. Other patterns
+/// are only checked with several parameters, which a type of a single value like a newtype, an
+/// alias or a date can't hold, so the struct must be destructured as its fields are unknown.
+fn check_struct_pattern(pat: &Pat, ty: &Type, params: &[String]) -> syn::Result<()> {
+  let fields = match pat {
+    Pat::TupleStruct(extractor) => match extractor.elems.first() {
+      Some(Pat::Struct(fields)) => Some(fields),
+      _ => None,
+    },
+    _ => None,
+  };
+
+  let Some(fields) = fields else {
+    return match struct_name(ty) {
+      Some(name) if params.len() > 1 => {
+        let msg = format!(
+          "Destructure `Path<{name}>` so its fields can be checked against the path parameters, \
+           e.g. `Path({name} {{ {}, .. }}): Path<{name}>`",
+          params.join(", ")
+        );
+        Err(Error::new(pat.span(), msg))
+      }
+      _ => Ok(()),
+    };
+  };
+
+  for field in &fields.fields {
+    let Member::Named(name) = &field.member else {
+      continue;
+    };
+
+    let name = name.to_string();
+    let name = name.trim_start_matches("r#");
+
+    if !params.iter().any(|param| param == name) {
+      return Err(Error::new(
+        field.member.span(),
+        format!("The route has no path parameter `{name}`"),
+      ));
+    }
+  }
+
+  Ok(())
+}
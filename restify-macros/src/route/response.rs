@@ -0,0 +1,234 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+  parse::{Parse, ParseStream},
+  Attribute, Error, Ident, LitInt, LitStr, Meta, ReturnType, Token, Type,
+};
+
+use crate::config::{config, RoutesConfig};
+
+/// Whether `name` is a header name `HeaderName::from_static` accepts once lowercased.
+pub fn valid_header_name(name: &str) -> bool {
+  let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+
+  !name.is_empty() && name.chars().all(is_token)
+}
+
+pub fn valid_header_value(value: &str) -> bool {
+  value.chars().all(|c| c == '\t' || (' '..='~').contains(&c))
+}
+
+/// The `#[status]`, `#[header]`, `#[return_json]` and `#[render]` attributes of a handler.
+#[derive(Default)]
+pub struct ResponseArgs {
+  pub status: Option<LitInt>,
+  pub headers: Vec<(LitStr, LitStr)>,
+  pub return_json: bool,
+  /// Set by `#[return_json(result)]`, the returned type is a `Result` whose name isn't `Result`,
+  /// e.g. an alias like `AppResult<T>`, so its `Ok` value is wrapped.
+  pub result: bool,
+  /// The template rendered with the returned context, and the `[views] dir` it's in.
+  pub render: Option<(LitStr, String)>,
+  /// Set by `#[sse]`, the returned stream is sent as server-sent events.
+  pub sse: bool,
+}
+
+impl ResponseArgs {
+  /// Parses `attr` into `self`, returns `false` if it isn't a response attribute.
+  pub fn parse_attr(&mut self, attr: &Attribute) -> syn::Result<bool> {
+    if attr.path().is_ident("status") {
+      let lit: LitInt = attr.parse_args()?;
+
+      if !(100..=999).contains(&lit.base10_parse::<u16>()?) {
+        return Err(Error::new_spanned(
+          lit,
+          "Status code must be between 100 and 999",
+        ));
+      }
+
+      if self.status.is_some() {
+        return Err(Error::new_spanned(
+          attr,
+          "`status` specified more than once",
+        ));
+      }
+
+      self.status = Some(lit);
+    } else if attr.path().is_ident("header") {
+      let HeaderArgs { name, value } = attr.parse_args()?;
+
+      if !valid_header_name(&name.value()) {
+        return Err(Error::new_spanned(name, "Invalid header name"));
+      }
+
+      if !valid_header_value(&value.value()) {
+        return Err(Error::new_spanned(value, "Invalid header value"));
+      }
+
+      let lowercase = name.value().to_ascii_lowercase();
+      self
+        .headers
+        .push((LitStr::new(&lowercase, name.span()), value));
+    } else if attr.path().is_ident("return_json") {
+      if let Meta::List(_) = &attr.meta {
+        let arg: Ident = attr.parse_args()?;
+
+        if arg != "result" {
+          return Err(Error::new_spanned(
+            arg,
+            "Expected `result`, as in `#[return_json(result)]` for a handler returning an alias \
+             of `Result`",
+          ));
+        }
+
+        self.result = true;
+      } else {
+        attr.meta.require_path_only()?;
+      }
+
+      if self.return_json {
+        return Err(Error::new_spanned(
+          attr,
+          "`return_json` specified more than once",
+        ));
+      }
+
+      if self.render.is_some() {
+        return Err(Error::new_spanned(
+          attr,
+          "`return_json` cannot be used with `render`",
+        ));
+      }
+
+      self.return_json = true;
+    } else if attr.path().is_ident("render") {
+      let template: LitStr = attr.parse_args()?;
+
+      if self.render.is_some() {
+        return Err(Error::new_spanned(
+          attr,
+          "`render` specified more than once",
+        ));
+      }
+
+      if self.return_json {
+        return Err(Error::new_spanned(
+          attr,
+          "`render` cannot be used with `return_json`",
+        ));
+      }
+
+      let config = config()?;
+      let dir = &config.views.dir;
+
+      if !config.root.join(dir).join(template.value()).is_file() {
+        let msg = format!("Template `{}` not found in `{dir}`", template.value());
+        return Err(Error::new_spanned(template, msg));
+      }
+
+      self.render = Some((template, dir.clone()));
+    } else {
+      return Ok(false);
+    }
+
+    Ok(true)
+  }
+
+  /// Adds the `[routes] headers` of `restify.toml` the handler doesn't set.
+  pub fn apply_defaults(&mut self, defaults: &RoutesConfig) {
+    for (name, value) in &defaults.headers {
+      if self.headers.iter().all(|(n, _)| n.value() != *name) {
+        let span = Span::call_site();
+        self
+          .headers
+          .push((LitStr::new(name, span), LitStr::new(value, span)));
+      }
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.status.is_none()
+      && self.headers.is_empty()
+      && !self.return_json
+      && self.render.is_none()
+      && !self.sse
+  }
+
+  /// Wraps `res`, the value returned by the handler, into the shaped response. `views` is the
+  /// `Views` extracted for `#[render]`.
+  pub fn shape(&self, res: TokenStream, output: &ReturnType, views: &Ident) -> TokenStream {
+    let wrap = if self.sse {
+      Some(quote!(::restify::axum::into_sse))
+    } else if let Some((name, dir)) = &self.render {
+      Some(quote! {
+        |context| #views.render(::restify::axum::Template { name: #name, dir: #dir }, context)
+      })
+    } else if self.return_json && !matches!(output, ReturnType::Default) {
+      Some(quote!(::axum::Json))
+    } else {
+      None
+    };
+
+    let res = match (wrap, output) {
+      (None, _) => res,
+      (Some(wrap), ReturnType::Type(_, ty)) if self.result || is_result(ty) => {
+        quote!(#res.map(#wrap))
+      }
+      (Some(wrap), _) => quote!((#wrap)(#res)),
+    };
+
+    if self.status.is_none() && self.headers.is_empty() {
+      return res;
+    }
+
+    let status = match &self.status {
+      Some(status) => quote! {
+        ::std::option::Option::Some(
+          ::axum::http::StatusCode::from_u16(#status).expect("valid status code")
+        )
+      },
+      None => quote!(::std::option::Option::None),
+    };
+
+    let (names, values): (Vec<_>, Vec<_>) = self.headers.iter().cloned().unzip();
+
+    quote! {
+      ::restify::axum::shape_response(
+        #res,
+        #status,
+        &[#((
+          ::axum::http::HeaderName::from_static(#names),
+          ::axum::http::HeaderValue::from_static(#values),
+        )),*],
+      )
+    }
+  }
+}
+
+struct HeaderArgs {
+  name: LitStr,
+  value: LitStr,
+}
+
+impl Parse for HeaderArgs {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let name = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let value = input.parse()?;
+    let _ = input.parse::<Token![,]>();
+
+    Ok(Self { name, value })
+  }
+}
+
+/// Whether `ty` is named `Result`, aliases need `#[return_json(result)]`.
+fn is_result(ty: &Type) -> bool {
+  match ty {
+    Type::Path(path) => path
+      .path
+      .segments
+      .last()
+      .is_some_and(|segment| segment.ident == "Result"),
+    _ => false,
+  }
+}
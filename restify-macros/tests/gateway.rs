@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use axum::{http::StatusCode, Json};
+use futures_util::{SinkExt, StreamExt};
+use restify::{
+  axum::{IntoRouter, WsClient, WsServer},
+  prelude::*,
+  testing::TestApp,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+#[derive(Clone)]
+struct AppState;
+
+#[derive(Deserialize, Serialize)]
+struct Todo {
+  list: String,
+  title: String,
+}
+
+#[derive(Injectable)]
+struct TodoGateway;
+
+#[gateway("/ws", state(AppState))]
+impl TodoGateway {
+  #[subscribe("todo.watch")]
+  async fn watch(&self, client: &WsClient, list: String) -> String {
+    client.join(list.clone());
+    list
+  }
+
+  #[subscribe("todo.create")]
+  async fn create(&self, client: &WsClient, todo: Todo) -> String {
+    client
+      .to(todo.list.clone())
+      .emit("todo.created", &todo)
+      .unwrap();
+    todo.title
+  }
+}
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo", state(AppState))]
+impl TodoController {
+  #[post("/")]
+  async fn create(self, ws: WsServer<TodoGateway>, Json(todo): Json<Todo>) -> StatusCode {
+    ws.to(todo.list.clone())
+      .emit("todo.created", &todo)
+      .unwrap();
+    StatusCode::CREATED
+  }
+}
+
+#[derive(Module)]
+#[module(controllers(TodoController), gateways(TodoGateway), state(AppState))]
+struct TodoModule;
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Serves the module on a free port, the HTTP requests are sent to the same router in process.
+async fn serve() -> (String, TestApp) {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  let router = TodoModule.into_router(&mut ()).with_state(AppState);
+  let app = TestApp::from_router(router.clone());
+
+  tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+  (addr.to_string(), app)
+}
+
+async fn send(socket: &mut Socket, event: &str, data: Value) {
+  let message = json!({ "event": event, "data": data }).to_string();
+  socket.send(Message::Text(message)).await.unwrap();
+}
+
+async fn receive(socket: &mut Socket) -> Value {
+  let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+    .await
+    .expect("a message before the timeout")
+    .unwrap()
+    .unwrap();
+
+  serde_json::from_str(message.to_text().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn replies_and_broadcasts() {
+  let (addr, app) = serve().await;
+  let (mut watcher, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+  let (mut creator, _) = connect_async(format!("ws://{addr}/ws")).await.unwrap();
+
+  send(&mut watcher, "todo.watch", json!("home")).await;
+  assert_eq!(
+    receive(&mut watcher).await,
+    json!({ "event": "todo.watch", "data": "home" })
+  );
+
+  let todo = json!({ "list": "home", "title": "Water the plants" });
+  send(&mut creator, "todo.create", todo.clone()).await;
+
+  assert_eq!(
+    receive(&mut creator).await,
+    json!({ "event": "todo.create", "data": "Water the plants" })
+  );
+  assert_eq!(
+    receive(&mut watcher).await,
+    json!({ "event": "todo.created", "data": todo })
+  );
+
+  let todo = json!({ "list": "home", "title": "Take out the trash" });
+  app
+    .post("/todo")
+    .json(&todo)
+    .await
+    .assert_status(StatusCode::CREATED);
+
+  assert_eq!(
+    receive(&mut watcher).await,
+    json!({ "event": "todo.created", "data": todo })
+  );
+}
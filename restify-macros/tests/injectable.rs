@@ -0,0 +1,877 @@
+mod from_state {
+  use axum::{extract::FromRef, routing::get, Router};
+  use restify::{prelude::*, testing::TestApp};
+
+  #[derive(Clone)]
+  struct Db(&'static str);
+
+  #[derive(Clone)]
+  struct AppState {
+    db: Db,
+  }
+
+  impl FromRef<AppState> for Db {
+    fn from_ref(state: &AppState) -> Self {
+      state.db.clone()
+    }
+  }
+
+  #[derive(Injectable)]
+  struct Repository {
+    #[injectable(from_state)]
+    db: Db,
+  }
+
+  #[derive(Injectable)]
+  struct Service {
+    repository: Repository,
+  }
+
+  #[derive(Injectable)]
+  struct TodoController {
+    service: Service,
+  }
+
+  #[controller("/todo", state(AppState))]
+  impl TodoController {
+    #[get("/")]
+    async fn db(self) -> &'static str {
+      self.service.repository.db.0
+    }
+  }
+
+  #[derive(Module)]
+  #[module(controllers(TodoController), state(AppState))]
+  struct TodoModule;
+
+  #[tokio::test]
+  async fn from_state_through_dependent_injectables() {
+    let app = TestApp::new(TodoModule).with_state(AppState { db: Db("todo") });
+
+    app.get("/todo").await.assert_text("todo");
+  }
+
+  #[derive(Clone)]
+  struct WorkerState {
+    db: Db,
+  }
+
+  impl FromRef<WorkerState> for Db {
+    fn from_ref(state: &WorkerState) -> Self {
+      state.db.clone()
+    }
+  }
+
+  #[tokio::test]
+  async fn any_state_containing_the_field() {
+    let router = Router::new()
+      .route(
+        "/",
+        get(|service: Service| async move { service.repository.db.0 }),
+      )
+      .with_state(WorkerState { db: Db("jobs") });
+
+    TestApp::from_router(router)
+      .get("/")
+      .await
+      .assert_text("jobs");
+  }
+}
+
+mod body {
+  use axum::{
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Json, Router,
+  };
+  use restify::{prelude::*, testing::TestApp};
+  use serde::Deserialize;
+
+  #[derive(Deserialize)]
+  struct CreateTodoDto {
+    title: String,
+  }
+
+  #[derive(Injectable)]
+  #[injectable(body)]
+  struct CreateTodo {
+    headers: HeaderMap,
+    #[injectable(via(Json))]
+    dto: CreateTodoDto,
+  }
+
+  fn app() -> TestApp {
+    let create = |input: CreateTodo| async move {
+      let user = input.headers["x-user"].to_str().unwrap().to_owned();
+      format!("{user}: {}", input.dto.title)
+    };
+
+    TestApp::from_router(Router::new().route("/todo", post(create)))
+  }
+
+  #[tokio::test]
+  async fn last_field_extracted_from_body() {
+    app()
+      .post("/todo")
+      .header("x-user", "ada")
+      .json(&serde_json::json!({ "title": "Write tests" }))
+      .await
+      .assert_status(StatusCode::OK)
+      .assert_text("ada: Write tests");
+  }
+
+  #[tokio::test]
+  async fn body_rejection_returned() {
+    app()
+      .post("/todo")
+      .header("x-user", "ada")
+      .body("{}")
+      .await
+      .assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+  }
+}
+
+mod field_options {
+  use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+  };
+  use restify::{prelude::*, testing::TestApp};
+  use serde::Deserialize;
+
+  struct ApiKey(String);
+
+  #[async_trait]
+  impl<S> FromRequestParts<S> for ApiKey
+  where
+    S: Send + Sync,
+  {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      let key = parts
+        .headers
+        .get("x-api-key")
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+      Ok(Self(key.to_str().unwrap().to_owned()))
+    }
+  }
+
+  struct Forbidden;
+
+  impl From<StatusCode> for Forbidden {
+    fn from(_: StatusCode) -> Self {
+      Self
+    }
+  }
+
+  impl IntoResponse for Forbidden {
+    fn into_response(self) -> Response {
+      (StatusCode::FORBIDDEN, "missing key").into_response()
+    }
+  }
+
+  #[derive(Default)]
+  struct Counter(u32);
+
+  #[derive(Default, Deserialize)]
+  struct Paging {
+    page: u32,
+  }
+
+  #[derive(Injectable)]
+  struct Listing {
+    #[injectable(skip)]
+    counter: Counter,
+    #[injectable(default, via(Query))]
+    paging: Paging,
+    #[injectable(optional)]
+    key: Option<ApiKey>,
+  }
+
+  #[derive(Injectable)]
+  struct Admin {
+    #[injectable(rejection(Forbidden))]
+    key: ApiKey,
+  }
+
+  fn app() -> TestApp {
+    let list = |listing: Listing| async move {
+      let key = listing.key.map(|key| key.0);
+      format!("{} {} {key:?}", listing.counter.0, listing.paging.page)
+    };
+    let admin = |admin: Admin| async move { admin.key.0 };
+
+    TestApp::from_router(
+      Router::new()
+        .route("/list", get(list))
+        .route("/admin", get(admin)),
+    )
+  }
+
+  #[tokio::test]
+  async fn skipped_defaulted_and_optional_fields() {
+    let app = app();
+
+    app.get("/list").await.assert_text("0 0 None");
+    app.get("/list?page=x").await.assert_text("0 0 None");
+    app
+      .get("/list?page=2")
+      .header("x-api-key", "secret")
+      .await
+      .assert_text("0 2 Some(\"secret\")");
+  }
+
+  #[tokio::test]
+  async fn field_rejection_converted() {
+    let app = app();
+
+    app
+      .get("/admin")
+      .await
+      .assert_status(StatusCode::FORBIDDEN)
+      .assert_text("missing key");
+    app
+      .get("/admin")
+      .header("x-api-key", "secret")
+      .await
+      .assert_text("secret");
+  }
+}
+
+mod generics {
+  use std::marker::PhantomData;
+
+  use axum::{extract::Query, http::HeaderMap, routing::get, Router};
+  use restify::{prelude::*, testing::TestApp};
+  use serde::Deserialize;
+
+  trait Entity: Send + 'static {
+    const NAME: &'static str;
+  }
+
+  struct User;
+  struct Post;
+
+  impl Entity for User {
+    const NAME: &'static str = "users";
+  }
+
+  impl Entity for Post {
+    const NAME: &'static str = "posts";
+  }
+
+  #[derive(Injectable)]
+  struct Repository<E: Entity> {
+    headers: HeaderMap,
+    #[injectable(skip)]
+    entity: PhantomData<E>,
+  }
+
+  impl<E: Entity> Repository<E> {
+    fn describe(&self) -> String {
+      format!(
+        "{} of {}",
+        E::NAME,
+        self.headers["x-tenant"].to_str().unwrap()
+      )
+    }
+  }
+
+  #[derive(Deserialize)]
+  struct Paging {
+    page: u32,
+  }
+
+  #[derive(Injectable)]
+  struct Paged<T>
+  where
+    T: Send,
+  {
+    #[injectable(via(Query))]
+    paging: Paging,
+    inner: T,
+  }
+
+  #[tokio::test]
+  async fn one_injectable_for_several_types() {
+    let list = |users: Repository<User>, posts: Paged<Repository<Post>>| async move {
+      format!(
+        "{}, {} page {}",
+        users.describe(),
+        posts.inner.describe(),
+        posts.paging.page
+      )
+    };
+    let app = TestApp::from_router(Router::new().route("/", get(list)));
+
+    app
+      .get("/?page=2")
+      .header("x-tenant", "acme")
+      .await
+      .assert_text("users of acme, posts of acme page 2");
+  }
+}
+
+mod enums {
+  use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    routing::get,
+    Router,
+  };
+  use restify::{prelude::*, testing::TestApp};
+
+  /// The value of the `x-<NAME>` header, or the `STATUS` rejection.
+  struct Header<const NAME: char, const STATUS: u16>(String);
+
+  #[async_trait]
+  impl<S, const NAME: char, const STATUS: u16> FromRequestParts<S> for Header<NAME, STATUS>
+  where
+    S: Send + Sync,
+  {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      match parts.headers.get(format!("x-{NAME}")) {
+        Some(value) => Ok(Self(value.to_str().unwrap().to_owned())),
+        None => Err(StatusCode::from_u16(STATUS).unwrap()),
+      }
+    }
+  }
+
+  type User = Header<'u', 401>;
+  type ApiKey = Header<'k', 403>;
+
+  #[derive(Injectable)]
+  enum Principal {
+    User(User),
+    Service { key: ApiKey },
+    Anonymous,
+  }
+
+  #[derive(Injectable)]
+  enum Authenticated {
+    User(User),
+    Service(ApiKey),
+  }
+
+  fn app() -> TestApp {
+    let principal = |principal: Principal| async move {
+      match principal {
+        Principal::User(user) => format!("user {}", user.0),
+        Principal::Service { key } => format!("service {}", key.0),
+        Principal::Anonymous => "anonymous".to_owned(),
+      }
+    };
+    let authenticated = |authenticated: Authenticated| async move {
+      match authenticated {
+        Authenticated::User(user) => user.0,
+        Authenticated::Service(key) => key.0,
+      }
+    };
+
+    TestApp::from_router(
+      Router::new()
+        .route("/principal", get(principal))
+        .route("/authenticated", get(authenticated)),
+    )
+  }
+
+  #[tokio::test]
+  async fn first_extracted_variant_wins() {
+    let app = app();
+
+    app
+      .get("/principal")
+      .header("x-u", "ada")
+      .header("x-k", "secret")
+      .await
+      .assert_text("user ada");
+    app
+      .get("/principal")
+      .header("x-k", "secret")
+      .await
+      .assert_text("service secret");
+    app.get("/principal").await.assert_text("anonymous");
+  }
+
+  #[tokio::test]
+  async fn rejection_of_last_variant_returned() {
+    app()
+      .get("/authenticated")
+      .await
+      .assert_status(StatusCode::FORBIDDEN);
+  }
+}
+
+mod lazy {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+  };
+  use restify::{axum::Lazy, prelude::*, testing::TestApp};
+
+  static EXTRACTED: AtomicUsize = AtomicUsize::new(0);
+
+  struct Mailer(String);
+
+  #[async_trait]
+  impl<S> FromRequestParts<S> for Mailer
+  where
+    S: Send + Sync,
+  {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      EXTRACTED.fetch_add(1, Ordering::SeqCst);
+
+      match parts.headers.get("x-smtp") {
+        Some(host) => Ok(Self(host.to_str().unwrap().to_owned())),
+        None => Err(StatusCode::SERVICE_UNAVAILABLE),
+      }
+    }
+  }
+
+  #[derive(Injectable)]
+  struct TodoService {
+    mailer: Lazy<Mailer>,
+  }
+
+  #[tokio::test]
+  async fn extracted_on_first_use_only() {
+    let skip = |service: TodoService| async move { service.mailer.is_resolved().to_string() };
+    let share = |service: TodoService| async move {
+      let first = service.mailer.get().await?.0.clone();
+      let second = service.mailer.get().await?.0.clone();
+      Ok::<_, Response>(format!("{first} {second}"))
+    };
+    let send = |service: TodoService| async move {
+      match service.mailer.into_inner().await {
+        Ok(mailer) => mailer.0.into_response(),
+        Err(rejection) => rejection,
+      }
+    };
+    let app = TestApp::from_router(
+      Router::new()
+        .route("/skip", get(skip))
+        .route("/share", get(share))
+        .route("/send", get(send)),
+    );
+
+    app
+      .get("/skip")
+      .header("x-smtp", "mail")
+      .await
+      .assert_text("false");
+    assert_eq!(EXTRACTED.load(Ordering::SeqCst), 0);
+
+    app
+      .get("/share")
+      .header("x-smtp", "mail")
+      .await
+      .assert_text("mail mail");
+    assert_eq!(EXTRACTED.load(Ordering::SeqCst), 1);
+
+    app
+      .get("/share")
+      .await
+      .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(EXTRACTED.load(Ordering::SeqCst), 2);
+
+    app
+      .get("/send")
+      .header("x-smtp", "mail")
+      .await
+      .assert_text("mail");
+    assert_eq!(EXTRACTED.load(Ordering::SeqCst), 3);
+  }
+}
+
+mod overrides {
+  use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  };
+
+  use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+  };
+  use restify::{
+    axum::{Overrides, Routes},
+    prelude::*,
+    testing::TestApp,
+    Module, ModuleDetails, Providers,
+  };
+
+  static CLOCKS_BUILT: AtomicUsize = AtomicUsize::new(0);
+  static FAKES_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+  #[derive(Clone)]
+  struct Clock(&'static str);
+
+  #[async_trait]
+  impl<S> FromRequestParts<S> for Clock
+  where
+    S: Send + Sync,
+  {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(_parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      CLOCKS_BUILT.fetch_add(1, Ordering::SeqCst);
+      Ok(Self("system"))
+    }
+  }
+
+  #[derive(Injectable, Clone)]
+  struct TodoService {
+    clock: Clock,
+  }
+
+  #[derive(Injectable)]
+  struct TodoController {
+    service: TodoService,
+  }
+
+  #[controller("/todo")]
+  impl TodoController {
+    #[get("/clock")]
+    async fn clock(self) -> &'static str {
+      self.service.clock.0
+    }
+  }
+
+  struct Greeting(&'static str);
+
+  /// Reads the `Greeting` provider the way the extractors of module providers do.
+  struct Greeter(Arc<Greeting>);
+
+  #[async_trait]
+  impl<S> FromRequestParts<S> for Greeter
+  where
+    S: Send + Sync,
+  {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      Overrides::resolve_provider::<Greeting>(parts)
+        .map(Self)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+  }
+
+  #[derive(Injectable)]
+  struct GreetingController {
+    greeter: Greeter,
+  }
+
+  #[controller("/greeting")]
+  impl GreetingController {
+    #[get("/")]
+    async fn greet(self) -> &'static str {
+      self.greeter.0 .0
+    }
+  }
+
+  struct GreetingModule;
+
+  impl Module for GreetingModule {
+    type Context = ();
+    type ControllerContext = ();
+    type ControllerReturn = Routes;
+
+    fn details(&self, _ctx: &mut ()) -> ModuleDetails<(), (), Routes> {
+      let mut providers = Providers::new();
+      providers.insert(Greeting("hello"));
+
+      ModuleDetails {
+        imports: vec![],
+        controllers: vec![],
+        gateways: vec![],
+        providers,
+      }
+    }
+  }
+
+  #[derive(Module)]
+  #[module(
+    imports(GreetingModule),
+    controllers(TodoController, GreetingController)
+  )]
+  struct AppModule;
+
+  #[tokio::test]
+  async fn injectables_replaced() {
+    TestApp::new(AppModule)
+      .get("/todo/clock")
+      .await
+      .assert_text("system");
+    assert_eq!(CLOCKS_BUILT.load(Ordering::SeqCst), 1);
+
+    let app = TestApp::new(AppModule).with_overrides(Overrides::new().injectable(TodoService {
+      clock: Clock("fake"),
+    }));
+
+    app.get("/todo/clock").await.assert_text("fake");
+    assert_eq!(CLOCKS_BUILT.load(Ordering::SeqCst), 1);
+
+    let app = TestApp::new(AppModule).with_overrides(Overrides::new().injectable_with(|| {
+      FAKES_BUILT.fetch_add(1, Ordering::SeqCst);
+      TodoService {
+        clock: Clock("built"),
+      }
+    }));
+
+    app.get("/todo/clock").await.assert_text("built");
+    app.get("/todo/clock").await.assert_text("built");
+    assert_eq!(FAKES_BUILT.load(Ordering::SeqCst), 2);
+    assert_eq!(CLOCKS_BUILT.load(Ordering::SeqCst), 1);
+  }
+
+  #[tokio::test]
+  async fn providers_replaced() {
+    TestApp::new(AppModule)
+      .get("/greeting")
+      .await
+      .assert_text("hello");
+
+    TestApp::new(AppModule)
+      .with_overrides(Overrides::new().provider(Greeting("hi")))
+      .get("/greeting")
+      .await
+      .assert_text("hi");
+  }
+}
+
+mod scope {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+  };
+  use restify::{prelude::*, testing::TestApp};
+
+  static SCOPED_BUILT: AtomicUsize = AtomicUsize::new(0);
+  static TRANSIENT_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+  /// The number of its extraction, counted in `SCOPED_BUILT` when `C` and in `TRANSIENT_BUILT`
+  /// otherwise.
+  #[derive(Clone)]
+  struct Built<const C: bool>(usize);
+
+  #[async_trait]
+  impl<S, const C: bool> FromRequestParts<S> for Built<C>
+  where
+    S: Send + Sync,
+  {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(_parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      let counter = if C { &SCOPED_BUILT } else { &TRANSIENT_BUILT };
+      Ok(Self(counter.fetch_add(1, Ordering::SeqCst) + 1))
+    }
+  }
+
+  #[derive(Injectable, Clone)]
+  struct RequestId {
+    built: Built<true>,
+  }
+
+  #[derive(Injectable, Clone)]
+  #[injectable(transient)]
+  struct AuditEntry {
+    built: Built<false>,
+  }
+
+  #[derive(Injectable)]
+  struct TodoService {
+    id: RequestId,
+  }
+
+  #[derive(Injectable)]
+  struct AuditService {
+    audit: AuditEntry,
+  }
+
+  #[derive(Injectable)]
+  struct TodoController {
+    service: TodoService,
+    id: RequestId,
+  }
+
+  #[controller("/todo")]
+  impl TodoController {
+    #[get("/id")]
+    async fn id(self) -> String {
+      format!("{} {}", self.id.built.0, self.service.id.built.0)
+    }
+  }
+
+  #[derive(Injectable)]
+  struct AuditController {
+    service: AuditService,
+    audit: AuditEntry,
+  }
+
+  #[controller("/audit")]
+  impl AuditController {
+    #[get("/")]
+    async fn audit(self) -> String {
+      format!("{} {}", self.audit.built.0, self.service.audit.built.0)
+    }
+  }
+
+  #[derive(Module)]
+  #[module(controllers(TodoController, AuditController))]
+  struct TodoModule;
+
+  #[tokio::test]
+  async fn built_once_per_request() {
+    let app = TestApp::new(TodoModule);
+
+    app.get("/todo/id").await.assert_text("1 1");
+    app.get("/todo/id").await.assert_text("2 2");
+    assert_eq!(SCOPED_BUILT.load(Ordering::SeqCst), 2);
+  }
+
+  #[tokio::test]
+  async fn transient_built_on_every_extraction() {
+    let app = TestApp::new(TodoModule);
+
+    app.get("/audit").await.assert_text("2 1");
+    app.get("/audit").await.assert_text("4 3");
+    assert_eq!(TRANSIENT_BUILT.load(Ordering::SeqCst), 4);
+  }
+}
+
+mod factory {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+  };
+  use restify::{prelude::*, testing::TestApp};
+
+  static CLIENTS_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+  struct Tenant(String);
+
+  #[async_trait]
+  impl<S> FromRequestParts<S> for Tenant
+  where
+    S: Send + Sync,
+  {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+      match parts.headers.get("x-tenant") {
+        Some(tenant) => Ok(Self(tenant.to_str().unwrap().to_owned())),
+        None => Err((StatusCode::BAD_REQUEST, "missing tenant")),
+      }
+    }
+  }
+
+  #[derive(Clone)]
+  struct RegionName(&'static str);
+
+  impl Default for RegionName {
+    fn default() -> Self {
+      Self("eu")
+    }
+  }
+
+  #[derive(Injectable, Clone)]
+  struct Region {
+    #[injectable(skip)]
+    name: RegionName,
+  }
+
+  #[derive(Injectable, Clone)]
+  #[injectable(factory = Self::build)]
+  struct TenantClient {
+    url: String,
+  }
+
+  impl TenantClient {
+    async fn build(tenant: Tenant, region: Region) -> Result<Self, (StatusCode, &'static str)> {
+      CLIENTS_BUILT.fetch_add(1, Ordering::SeqCst);
+
+      if tenant.0 == "blocked" {
+        return Err((StatusCode::FORBIDDEN, "blocked tenant"));
+      }
+
+      Ok(Self {
+        url: format!("https://{}.{}.example.com", tenant.0, region.name.0),
+      })
+    }
+  }
+
+  #[derive(Injectable)]
+  struct TodoService {
+    client: TenantClient,
+  }
+
+  #[derive(Injectable)]
+  struct TodoController {
+    service: TodoService,
+    client: TenantClient,
+  }
+
+  #[controller("/todo")]
+  impl TodoController {
+    #[get("/client")]
+    async fn client(self) -> String {
+      assert_eq!(self.client.url, self.service.client.url);
+      self.client.url
+    }
+  }
+
+  #[derive(Module)]
+  #[module(controllers(TodoController))]
+  struct TodoModule;
+
+  #[tokio::test]
+  async fn built_from_the_arguments() {
+    let app = TestApp::new(TodoModule);
+
+    app
+      .get("/todo/client")
+      .header("x-tenant", "acme")
+      .await
+      .assert_status(StatusCode::OK)
+      .assert_text("https://acme.eu.example.com");
+    assert_eq!(CLIENTS_BUILT.load(Ordering::SeqCst), 1);
+
+    app
+      .get("/todo/client")
+      .await
+      .assert_status(StatusCode::BAD_REQUEST)
+      .assert_text("missing tenant");
+    assert_eq!(CLIENTS_BUILT.load(Ordering::SeqCst), 1);
+
+    app
+      .get("/todo/client")
+      .header("x-tenant", "blocked")
+      .await
+      .assert_status(StatusCode::FORBIDDEN)
+      .assert_text("blocked tenant");
+    assert_eq!(CLIENTS_BUILT.load(Ordering::SeqCst), 2);
+  }
+}
@@ -0,0 +1,43 @@
+use std::borrow::Cow;
+
+use axum::{async_trait, http::StatusCode};
+use restify::{
+  axum::{Health, HealthIndicator, HealthModule},
+  prelude::*,
+  testing::TestApp,
+};
+
+struct DatabaseIndicator;
+
+#[async_trait]
+impl HealthIndicator for DatabaseIndicator {
+  fn name(&self) -> Cow<'static, str> {
+    "db".into()
+  }
+
+  async fn check(&self, _state: &()) -> Health {
+    Health::down()
+  }
+}
+
+#[derive(Module)]
+#[module(health(DatabaseIndicator))]
+struct TodoModule;
+
+#[derive(Module)]
+#[module(imports(HealthModule::new(), TodoModule))]
+struct AppModule;
+
+#[tokio::test]
+async fn health_indicators_of_imported_modules() {
+  let app = TestApp::new(AppModule).with_state(());
+
+  app.get("/health/live").await.assert_status(StatusCode::OK);
+
+  let res = app.get("/health/ready").await;
+  res.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(
+    res.text(),
+    r#"{"status":"down","checks":{"db":{"status":"down"}}}"#
+  );
+}
@@ -0,0 +1,112 @@
+use axum::http::StatusCode;
+use restify::{prelude::*, testing::TestApp};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct Todo {
+  title: &'static str,
+}
+
+type AppResult<T> = Result<T, (StatusCode, &'static str)>;
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[post("/")]
+  #[status(201)]
+  #[header("Cache-Control", "no-store")]
+  #[return_json]
+  async fn create(self) -> Todo {
+    Todo { title: "Write tests" }
+  }
+
+  #[post("/invalid")]
+  #[status(201)]
+  async fn invalid(self) -> (StatusCode, &'static str) {
+    (StatusCode::BAD_REQUEST, "invalid")
+  }
+
+  #[get("/cached")]
+  #[header("Cache-Control", "no-store")]
+  async fn cached(self) -> ([(&'static str, &'static str); 1], &'static str) {
+    ([("cache-control", "max-age=60")], "cached")
+  }
+
+  #[get("/found")]
+  #[return_json]
+  async fn found(self) -> Result<Todo, StatusCode> {
+    Ok(Todo { title: "found" })
+  }
+
+  #[get("/missing")]
+  #[return_json(result)]
+  async fn missing(self) -> AppResult<Todo> {
+    Err((StatusCode::NOT_FOUND, "missing"))
+  }
+
+  #[get("/aliased")]
+  #[return_json(result)]
+  async fn aliased(self) -> AppResult<Todo> {
+    Ok(Todo { title: "aliased" })
+  }
+}
+
+#[derive(Module)]
+#[module(controllers(TodoController))]
+struct TodoModule;
+
+fn app() -> TestApp {
+  TestApp::new(TodoModule).with_state(())
+}
+
+#[tokio::test]
+async fn status_only_replaces_ok() {
+  let app = app();
+
+  app
+    .post("/todo")
+    .await
+    .assert_status(StatusCode::CREATED)
+    .assert_header("cache-control", "no-store")
+    .assert_json(&json!({ "title": "Write tests" }));
+
+  app
+    .post("/todo/invalid")
+    .await
+    .assert_status(StatusCode::BAD_REQUEST)
+    .assert_text("invalid");
+}
+
+#[tokio::test]
+async fn headers_of_the_handler_take_precedence() {
+  app()
+    .get("/todo/cached")
+    .await
+    .assert_status(StatusCode::OK)
+    .assert_header("cache-control", "max-age=60");
+}
+
+#[tokio::test]
+async fn ok_values_wrapped_in_json() {
+  let app = app();
+
+  app
+    .get("/todo/found")
+    .await
+    .assert_header("content-type", "application/json")
+    .assert_json(&json!({ "title": "found" }));
+
+  app
+    .get("/todo/aliased")
+    .await
+    .assert_json(&json!({ "title": "aliased" }));
+
+  app
+    .get("/todo/missing")
+    .await
+    .assert_status(StatusCode::NOT_FOUND)
+    .assert_text("missing");
+}
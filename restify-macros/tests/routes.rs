@@ -0,0 +1,60 @@
+use axum::{
+  extract::Path,
+  http::{Method, StatusCode},
+};
+use restify::{prelude::*, testing::TestApp};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct TodoId(String);
+
+type ListId = u64;
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[get("/{id}")]
+  async fn get(self, Path(id): Path<TodoId>) -> String {
+    id.0
+  }
+
+  #[get("/list/{id}")]
+  async fn list(self, Path(id): Path<ListId>) -> String {
+    id.to_string()
+  }
+
+  #[route("/search", method = "M-SEARCH")]
+  async fn search(self) -> &'static str {
+    "found"
+  }
+}
+
+#[derive(Module)]
+#[module(controllers(TodoController))]
+struct TodoModule;
+
+#[tokio::test]
+async fn single_path_types_that_arent_scalars() {
+  let app = TestApp::new(TodoModule).with_state(());
+
+  app.get("/todo/abc").await.assert_text("abc");
+  app.get("/todo/list/7").await.assert_text("7");
+}
+
+#[tokio::test]
+async fn hyphenated_custom_methods() {
+  let app = TestApp::new(TodoModule).with_state(());
+  let m_search = Method::from_bytes(b"M-SEARCH").unwrap();
+
+  let res = app.request(m_search, "/todo/search").await;
+  res.assert_status(StatusCode::OK);
+  res.assert_text("found");
+
+  app
+    .get("/todo/search")
+    .await
+    .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+}
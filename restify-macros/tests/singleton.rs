@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use axum::extract::FromRef;
+use restify::{
+  axum::{FromProviders, Routes},
+  prelude::*,
+  testing::TestApp,
+  Module, ModuleDetails, Providers,
+};
+
+static STATS_BUILT: AtomicUsize = AtomicUsize::new(0);
+static GREETING_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct AppState {
+  stats: Vec<u64>,
+}
+
+struct StatsController {
+  total: u64,
+}
+
+impl FromRef<AppState> for StatsController {
+  fn from_ref(state: &AppState) -> Self {
+    STATS_BUILT.fetch_add(1, Ordering::SeqCst);
+    StatsController {
+      total: state.stats.iter().sum(),
+    }
+  }
+}
+
+#[controller("/stats", singleton, state(AppState))]
+impl StatsController {
+  #[get("/")]
+  async fn total(&self) -> String {
+    self.total.to_string()
+  }
+}
+
+struct Greeting(&'static str);
+
+struct SettingsModule;
+
+impl Module for SettingsModule {
+  type Context = ();
+  type ControllerContext = ();
+  type ControllerReturn = Routes<AppState>;
+
+  fn details(&self, _ctx: &mut ()) -> ModuleDetails<(), (), Routes<AppState>> {
+    let mut providers = Providers::new();
+    providers.insert(Greeting("hello"));
+
+    ModuleDetails {
+      imports: vec![],
+      controllers: vec![],
+      gateways: vec![],
+      providers,
+    }
+  }
+}
+
+struct GreetingController {
+  greeting: &'static str,
+}
+
+impl FromProviders for GreetingController {
+  fn from_providers(providers: &Providers) -> Self {
+    GREETING_BUILT.fetch_add(1, Ordering::SeqCst);
+    GreetingController {
+      greeting: providers.get::<Greeting>().map_or("missing", |greeting| greeting.0),
+    }
+  }
+}
+
+#[controller("/greeting", singleton(providers), state(AppState))]
+impl GreetingController {
+  #[get("/")]
+  async fn greet(&self) -> &'static str {
+    self.greeting
+  }
+}
+
+#[derive(Module)]
+#[module(
+  imports(SettingsModule),
+  controllers(StatsController, GreetingController),
+  state(AppState)
+)]
+struct AppModule;
+
+fn app() -> TestApp {
+  TestApp::new(AppModule).with_state(AppState {
+    stats: vec![1, 2, 3],
+  })
+}
+
+#[tokio::test]
+async fn built_once_from_the_state() {
+  let app = app();
+
+  for _ in 0..3 {
+    app.get("/stats").await.assert_text("6");
+  }
+
+  assert_eq!(STATS_BUILT.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn built_once_from_the_providers() {
+  let app = app();
+
+  for _ in 0..3 {
+    app.get("/greeting").await.assert_text("hello");
+  }
+
+  assert_eq!(GREETING_BUILT.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,80 @@
+use axum::{http::StatusCode, Router};
+use futures_util::{stream, Stream};
+use restify::{
+  axum::{IntoRouter, LastEventId, SseEvent},
+  prelude::*,
+  testing::TestApp,
+};
+use serde::Serialize;
+
+#[derive(Clone, Serialize)]
+struct Todo {
+  id: u32,
+  title: &'static str,
+}
+
+const TODOS: [Todo; 3] = [
+  Todo {
+    id: 1,
+    title: "Write",
+  },
+  Todo {
+    id: 2,
+    title: "Test",
+  },
+  Todo {
+    id: 3,
+    title: "Ship",
+  },
+];
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[sse("/events")]
+  async fn events(self, LastEventId(last): LastEventId) -> impl Stream<Item = SseEvent<Todo>> {
+    let last: u32 = last.map_or(0, |id| id.parse().unwrap());
+    let todos = TODOS.into_iter().filter(move |todo| todo.id > last);
+
+    stream::iter(todos.map(|todo| SseEvent::new(todo.clone()).event("update").id(todo.id)))
+  }
+}
+
+#[derive(Module)]
+#[module(controllers(TodoController))]
+struct TodoModule;
+
+fn app() -> TestApp {
+  let router: Router = TodoModule.into_router(&mut ());
+
+  TestApp::from_router(router)
+}
+
+#[tokio::test]
+async fn streams_events() {
+  let res = app().get("/todo/events").await;
+
+  res.assert_status(StatusCode::OK);
+  res.assert_header("content-type", "text/event-stream");
+  assert_eq!(
+    res.text(),
+    concat!(
+      "data: {\"id\":1,\"title\":\"Write\"}\nevent: update\nid: 1\n\n",
+      "data: {\"id\":2,\"title\":\"Test\"}\nevent: update\nid: 2\n\n",
+      "data: {\"id\":3,\"title\":\"Ship\"}\nevent: update\nid: 3\n\n",
+    )
+  );
+}
+
+#[tokio::test]
+async fn resumes_after_last_event_id() {
+  let res = app().get("/todo/events").header("last-event-id", "2").await;
+
+  res.assert_status(StatusCode::OK);
+  assert_eq!(
+    res.text(),
+    "data: {\"id\":3,\"title\":\"Ship\"}\nevent: update\nid: 3\n\n"
+  );
+}
@@ -0,0 +1,12 @@
+use axum::http::HeaderMap;
+use restify::prelude::*;
+
+#[derive(Injectable)]
+#[injectable(body)]
+struct Input {
+  headers: HeaderMap,
+  #[injectable(skip)]
+  count: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use axum::http::HeaderMap;
+use restify::prelude::*;
+
+#[derive(Injectable)]
+#[injectable(body)]
+enum Input {
+  Headers(HeaderMap),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use axum::{extract::State, Json};
+use restify::prelude::*;
+
+#[derive(Injectable)]
+#[injectable(body, via(State))]
+struct Input {
+  body: Json<String>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use restify::prelude::*;
+
+#[derive(Injectable)]
+#[injectable(body)]
+struct Input;
+
+fn main() {}
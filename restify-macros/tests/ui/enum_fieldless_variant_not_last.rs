@@ -0,0 +1,10 @@
+use axum::http::HeaderMap;
+use restify::prelude::*;
+
+#[derive(Injectable)]
+enum Principal {
+  Anonymous,
+  Headers(HeaderMap),
+}
+
+fn main() {}
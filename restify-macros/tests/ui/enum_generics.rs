@@ -0,0 +1,9 @@
+use restify::prelude::*;
+
+#[derive(Injectable)]
+enum Principal<T> {
+  Known(T),
+  Anonymous,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use axum::http::HeaderMap;
+use restify::prelude::*;
+
+#[derive(Injectable)]
+enum Principal {
+  #[injectable(optional)]
+  Headers(HeaderMap),
+  Anonymous,
+}
+
+fn main() {}
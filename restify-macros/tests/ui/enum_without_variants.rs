@@ -0,0 +1,6 @@
+use restify::prelude::*;
+
+#[derive(Injectable)]
+enum Principal {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use axum::http::HeaderMap;
+use restify::prelude::*;
+
+#[derive(Injectable)]
+struct Service {
+  #[injectable(optional, default)]
+  headers: Option<HeaderMap>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use axum::http::{HeaderMap, StatusCode};
+use restify::prelude::*;
+
+#[derive(Injectable)]
+struct Service {
+  #[injectable(default, rejection(StatusCode))]
+  headers: HeaderMap,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use restify::prelude::*;
+
+#[derive(Injectable)]
+struct Service {
+  #[injectable(skip, default)]
+  count: u32,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use axum::extract::FromRequestParts;
+use restify::prelude::*;
+
+#[derive(Clone)]
+struct Db;
+
+#[derive(Clone)]
+struct AppState;
+
+#[derive(Injectable)]
+struct Repository {
+  #[injectable(from_state)]
+  db: Db,
+}
+
+#[derive(Injectable)]
+struct Service {
+  repository: Repository,
+}
+
+fn extract<T: FromRequestParts<AppState>>() {}
+
+fn main() {
+  extract::<Service>();
+}
@@ -0,0 +1,13 @@
+use restify::prelude::*;
+
+#[derive(Clone)]
+struct Db;
+
+#[derive(Injectable)]
+#[injectable(body)]
+struct Input {
+  #[injectable(from_state)]
+  db: Db,
+}
+
+fn main() {}
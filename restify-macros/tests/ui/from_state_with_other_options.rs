@@ -0,0 +1,13 @@
+use axum::extract::State;
+use restify::prelude::*;
+
+#[derive(Clone)]
+struct Db;
+
+#[derive(Injectable)]
+struct Repository {
+  #[injectable(from_state, via(State))]
+  db: Db,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use restify::{axum::WsClient, prelude::*};
+
+#[derive(Injectable)]
+struct TodoGateway;
+
+#[gateway("/ws")]
+impl TodoGateway {
+  #[subscribe("todo.watch")]
+  async fn watch(&self, client: &WsClient, list: &str) {
+    client.join(list);
+  }
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use axum::{extract::FromRequestParts, http::HeaderMap};
+use restify::prelude::*;
+
+#[derive(Injectable)]
+struct Wrapper<T: Send> {
+  headers: HeaderMap,
+  inner: T,
+}
+
+fn extract<T: FromRequestParts<()>>() {}
+
+fn main() {
+  extract::<Wrapper<HeaderMap>>();
+  extract::<Wrapper<u32>>();
+}
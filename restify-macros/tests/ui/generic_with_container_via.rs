@@ -0,0 +1,11 @@
+use axum::extract::State;
+use restify::prelude::*;
+
+#[derive(Injectable)]
+#[injectable(via(State))]
+struct Cache<T, U> {
+  left: T,
+  right: U,
+}
+
+fn main() {}
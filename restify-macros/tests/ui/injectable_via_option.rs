@@ -0,0 +1,17 @@
+use axum::Json;
+use restify::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Filter {
+  done: bool,
+}
+
+#[derive(Injectable)]
+#[injectable(body)]
+struct Search {
+  #[injectable(via(Json))]
+  filter: Option<Filter>,
+}
+
+fn main() {}
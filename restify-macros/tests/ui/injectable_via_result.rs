@@ -0,0 +1,17 @@
+use axum::{extract::rejection::JsonRejection, Json};
+use restify::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Filter {
+  done: bool,
+}
+
+#[derive(Injectable)]
+#[injectable(body)]
+struct Search {
+  #[injectable(via(Json))]
+  filter: Result<Filter, JsonRejection>,
+}
+
+fn main() {}
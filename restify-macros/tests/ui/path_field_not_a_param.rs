@@ -0,0 +1,22 @@
+use axum::extract::Path;
+use restify::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct TodoPath {
+  list: String,
+  id: String,
+}
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[get("/{id}")]
+  async fn get(self, Path(TodoPath { list, id }): Path<TodoPath>) -> String {
+    list + &id
+  }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use axum::extract::Path;
+use restify::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct TodoPath {
+  list: String,
+  id: String,
+}
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[get("/{list}/{id}")]
+  async fn get(self, todo: Path<TodoPath>) -> String {
+    todo.0.id
+  }
+}
+
+fn main() {}
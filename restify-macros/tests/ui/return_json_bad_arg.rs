@@ -0,0 +1,15 @@
+use restify::prelude::*;
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[get("/")]
+  #[return_json(ok)]
+  async fn get(self) -> Result<String, String> {
+    Ok("todo".into())
+  }
+}
+
+fn main() {}
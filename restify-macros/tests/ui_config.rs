@@ -0,0 +1,12 @@
+/// The errors of `restify.toml`, in a test binary of their own as the file is set for the whole
+/// process.
+#[test]
+fn ui_config() {
+  std::env::set_var(
+    "RESTIFY_CONFIG",
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/ui_config/restify.toml"),
+  );
+
+  let t = trybuild::TestCases::new();
+  t.compile_fail("tests/ui_config/*.rs");
+}
@@ -0,0 +1,71 @@
+use axum::{http::StatusCode, Extension, Router};
+use restify::{
+  axum::{IntoRouter, MiniJinja, RenderFailure, Template, Views},
+  prelude::*,
+  testing::TestApp,
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct TodoList {
+  title: &'static str,
+}
+
+#[derive(Injectable)]
+struct TodoController;
+
+#[controller("/todo")]
+impl TodoController {
+  #[get("/")]
+  #[render("todo/list.html")]
+  async fn list(self) -> TodoList {
+    TodoList { title: "Todos" }
+  }
+
+  #[get("/broken")]
+  #[render("todo/broken.html")]
+  async fn broken(self) -> TodoList {
+    TodoList { title: "Todos" }
+  }
+}
+
+#[derive(Module)]
+#[module(controllers(TodoController))]
+struct TodoModule;
+
+fn app() -> TestApp {
+  let views = Views::new(MiniJinja::new().root(env!("CARGO_MANIFEST_DIR")));
+  let router: Router = TodoModule.into_router(&mut ()).layer(Extension(views));
+
+  TestApp::from_router(router)
+}
+
+#[tokio::test]
+async fn renders_templates() {
+  let res = app().get("/todo").await;
+
+  res.assert_status(StatusCode::OK);
+  res.assert_header("content-type", "text/html; charset=utf-8");
+  assert_eq!(res.text(), "<h1>Todos</h1>");
+}
+
+#[tokio::test]
+async fn render_errors_kept_in_the_response() {
+  let views = Views::new(MiniJinja::new().root(env!("CARGO_MANIFEST_DIR")));
+  let template = Template {
+    name: "todo/broken.html",
+    dir: "templates",
+  };
+
+  let res = views.render(template, TodoList { title: "Todos" });
+  assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+  let failure = res.extensions().get::<RenderFailure>().unwrap();
+  assert_eq!(failure.template.name, "todo/broken.html");
+  assert!(failure.error.to_string().contains("undefined"));
+
+  app()
+    .get("/todo/broken")
+    .await
+    .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -45,7 +45,7 @@ pub struct MethodRouter<S = (), E = Infallible> {
 
 pub struct RouteWrapper<S, E = Infallible> {
   path: String,
-  method: MethodRouter<S, E>
+  method: MethodRouter<S, E>,
 }
 
 impl<S> MethodRouter<S, Infallible>
@@ -158,20 +158,19 @@ where
   }
 }
 
-
 impl<S, E> DefinitionHolder for RouteWrapper<S, E>
 where
   S: Clone,
 {
-    fn path(&self) -> &str {
-        &self.path
-    }
+  fn path(&self) -> &str {
+    &self.path
+  }
 
-    fn operations(&mut self) -> indexmap::IndexMap<OperationType, Operation> {
-        todo!()
-    }
+  fn operations(&mut self) -> indexmap::IndexMap<OperationType, Operation> {
+    todo!()
+  }
 
-    fn components(&mut self) -> Vec<Components> {
-        todo!()
-    }
-}
\ No newline at end of file
+  fn components(&mut self) -> Vec<Components> {
+    todo!()
+  }
+}
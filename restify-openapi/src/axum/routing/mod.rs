@@ -10,7 +10,10 @@ use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use restify_core::path::PathTemplate;
+
 use crate::{
+  path_item,
   paths::{OperationType, Paths},
   Components, DefinitionHolder,
 };
@@ -33,8 +36,20 @@ where
     }
   }
 
-  pub fn route(self, path: &str, method_router: MethodRouter<S>) -> Self {
-    self.inner = self.inner.route(path, method_router.inner);
+  /// Adds a route, with a path in the syntax of `restify::path::PathTemplate`, and its item in the
+  /// paths of the document.
+  #[track_caller]
+  pub fn route(mut self, path: &str, method_router: MethodRouter<S>) -> Self {
+    let template = PathTemplate::parse(path).unwrap_or_else(|err| panic!("{err}"));
+    let (key, mut item) = path_item(&template);
+
+    item.operations = method_router.def.operations;
+    self.paths.paths.insert(key, item);
+    for components in method_router.components {
+      self.components.merge(components);
+    }
+    self.inner = self.inner.route(&template.to_axum(), method_router.inner);
+    self
   }
 
   fn update_from_def_holder(&mut self, definition_holder: &mut impl DefinitionHolder) {
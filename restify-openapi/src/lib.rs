@@ -5,6 +5,7 @@ mod definition_holder;
 mod error_component;
 mod models;
 mod path_item_definition;
+mod path_template;
 mod simple;
 
 pub use api_component::ApiComponent;
@@ -13,3 +14,4 @@ pub use definition_holder::*;
 pub use error_component::ApiErrorComponent;
 pub use models::*;
 pub use path_item_definition::*;
+pub use path_template::*;
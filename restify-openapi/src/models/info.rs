@@ -4,7 +4,10 @@ use serde_json::Value;
 
 /// The object provides metadata about the API. The metadata MAY be used by the clients if needed, and MAY be presented in editing or documentation generation tools for convenience.
 #[derive(Serialize, Clone, Debug, Default)]
-#[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize, PartialEq))]
+#[cfg_attr(
+  any(test, feature = "deserialize"),
+  derive(serde::Deserialize, PartialEq)
+)]
 #[serde(rename_all = "camelCase")]
 pub struct Info {
   /// The title of the API
@@ -24,13 +27,20 @@ pub struct Info {
   /// The version of the OpenAPI document (which is distinct from the [OpenAPI Specification version](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#oasVersion) or the API implementation version).
   pub version: String,
   /// This object MAY be extended with [Specification Extensions](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#specification-extensions).
-  #[serde(flatten, skip_serializing_if = "IndexMap::is_empty", skip_deserializing)]
+  #[serde(
+    flatten,
+    skip_serializing_if = "IndexMap::is_empty",
+    skip_deserializing
+  )]
   pub extensions: IndexMap<String, Value>,
 }
 
 /// Contact information for the exposed API.
 #[derive(Serialize, Clone, Debug, Default)]
-#[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize, PartialEq))]
+#[cfg_attr(
+  any(test, feature = "deserialize"),
+  derive(serde::Deserialize, PartialEq)
+)]
 #[serde(rename_all = "camelCase")]
 pub struct Contact {
   /// The identifying name of the contact person/organization.
@@ -43,13 +53,20 @@ pub struct Contact {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub email: Option<String>,
   /// This object MAY be extended with [Specification Extensions](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#specification-extensions).
-  #[serde(flatten, skip_serializing_if = "IndexMap::is_empty", skip_deserializing)]
+  #[serde(
+    flatten,
+    skip_serializing_if = "IndexMap::is_empty",
+    skip_deserializing
+  )]
   pub extensions: IndexMap<String, Value>,
 }
 
 /// License information for the exposed API.
 #[derive(Serialize, Clone, Debug, Default)]
-#[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize, PartialEq))]
+#[cfg_attr(
+  any(test, feature = "deserialize"),
+  derive(serde::Deserialize, PartialEq)
+)]
 #[serde(rename_all = "camelCase")]
 pub struct License {
   /// The license name used for the API.
@@ -58,6 +75,10 @@ pub struct License {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub url: Option<String>,
   /// This object MAY be extended with [Specification Extensions](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#specification-extensions).
-  #[serde(flatten, skip_serializing_if = "IndexMap::is_empty", skip_deserializing)]
+  #[serde(
+    flatten,
+    skip_serializing_if = "IndexMap::is_empty",
+    skip_deserializing
+  )]
   pub extensions: IndexMap<String, Value>,
 }
@@ -25,22 +25,17 @@ pub mod tag;
 
 pub use schemars::schema::*;
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, Default)]
 #[cfg_attr(
   any(test, feature = "deserialize"),
   derive(serde::Deserialize, PartialEq)
 )]
 pub enum OpenApiVersion {
+  #[default]
   #[serde(rename = "3.0.3")]
   OAS3_0,
 }
 
-impl Default for OpenApiVersion {
-  fn default() -> Self {
-    Self::OAS3_0
-  }
-}
-
 /// This is the root document object of the [OpenAPI document](https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#openapi-document).
 #[derive(Serialize, Clone, Debug, Default)]
 #[cfg_attr(
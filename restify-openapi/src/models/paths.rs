@@ -308,22 +308,17 @@ pub struct Header {
   pub style: Option<ParameterStyle>,
 }
 
-#[derive(Serialize, Clone, Debug, Eq, PartialEq)]
+#[derive(Serialize, Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize))]
 #[serde(rename_all = "lowercase")]
 pub enum ParameterIn {
   Query,
   Header,
+  #[default]
   Path,
   Cookie,
 }
 
-impl Default for ParameterIn {
-  fn default() -> Self {
-    Self::Path
-  }
-}
-
 #[derive(Serialize, Clone, Debug)]
 #[cfg_attr(
   any(test, feature = "deserialize"),
@@ -2,7 +2,10 @@ use schemars::schema::Schema;
 use serde::Serialize;
 
 #[derive(Serialize, Clone, Debug)]
-#[cfg_attr(any(test, feature = "deserialize"), derive(serde::Deserialize, PartialEq))]
+#[cfg_attr(
+  any(test, feature = "deserialize"),
+  derive(serde::Deserialize, PartialEq)
+)]
 #[serde(untagged)]
 pub enum ReferenceOr<T: Clone> {
   Object(T),
@@ -0,0 +1,76 @@
+use restify_core::path::{PathTemplate, Segment};
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+
+use crate::{
+  paths::{Parameter, ParameterDefinition, ParameterIn, PathItem},
+  reference_or::ReferenceOr,
+};
+
+/// The key of `path` in the paths of the document, e.g. `/todo/{id}` for `/todo/{id:uuid}`, with
+/// an item declaring its parameters and their types.
+pub fn path_item(path: &PathTemplate) -> (String, PathItem) {
+  let parameters = path
+    .segments()
+    .iter()
+    .filter_map(|segment| match segment {
+      Segment::Static(_) => None,
+      Segment::Param { name, kind } => Some((name, kind.openapi_type())),
+      Segment::Wildcard { name } => Some((name, ("string", None))),
+    })
+    .map(|(name, (ty, format))| {
+      let instance_type = match ty {
+        "integer" => InstanceType::Integer,
+        _ => InstanceType::String,
+      };
+      let schema = Schema::Object(SchemaObject {
+        instance_type: Some(instance_type.into()),
+        format: format.map(str::to_owned),
+        ..Default::default()
+      });
+
+      ReferenceOr::Object(Parameter {
+        name: name.clone(),
+        _in: ParameterIn::Path,
+        required: Some(true),
+        definition: Some(ParameterDefinition::Schema(schema.into())),
+        ..Default::default()
+      })
+    })
+    .collect();
+
+  let item = PathItem {
+    parameters,
+    ..Default::default()
+  };
+
+  (path.to_openapi(), item)
+}
+
+#[cfg(test)]
+mod test {
+  #![allow(clippy::expect_used)]
+
+  use restify_core::path::PathTemplate;
+  use serde_json::json;
+
+  use super::path_item;
+
+  #[test]
+  fn typed_params_in_openapi() {
+    let path = PathTemplate::parse("/todo/{id:uuid}").expect("valid path");
+    let (key, item) = path_item(&path);
+
+    assert_eq!(key, "/todo/{id}");
+    assert_eq!(
+      serde_json::to_value(&item).expect("Error generating json for path item"),
+      json!({
+        "parameters": [{
+          "name": "id",
+          "in": "path",
+          "required": true,
+          "schema": { "type": "string", "format": "uuid" },
+        }],
+      })
+    );
+  }
+}
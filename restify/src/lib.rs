@@ -4,7 +4,7 @@ pub use restify_macros::*;
 pub use restify_core::*;
 
 pub mod prelude {
-  pub use restify_core::{BoxedControllerFn, BoxedModule, Controller, Module};
+  pub use restify_core::{BoxedControllerFn, BoxedModule, Controller, Gateway, Module};
   #[cfg(feature = "macros")]
-  pub use restify_macros::{controller, Injectable, Module};
+  pub use restify_macros::{controller, gateway, Injectable, Module};
 }